@@ -1,5 +1,6 @@
-use crate::{errors::BindingError, types::module::Module};
+use crate::{errors::BindingError, lexer::Span, types::module::Module};
 use std::{
+    any::Any,
     cell::RefCell,
     collections::HashMap,
     rc::{Rc, Weak},
@@ -8,9 +9,19 @@ use std::{
 pub trait AstNode {
     fn get_meaning(&self) -> Meaning;
     fn get_name(&self) -> String;
+    fn get_span(&self) -> Span;
+    /// The span of the name alone, which is what redeclaration errors point at.
+    fn get_name_span(&self) -> Span {
+        self.get_span()
+    }
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>>;
+    fn get_locals(&self) -> Option<&RefCell<Table>> {
+        None
+    }
+    fn as_any(&self) -> &dyn Any;
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Meaning {
     Value,
     Type,
@@ -18,22 +29,34 @@ pub enum Meaning {
 
 pub type Table = HashMap<String, Symbol>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Symbol {
     pub declarations: RefCell<Vec<Weak<dyn AstNode>>>,
 }
 
+impl Symbol {
+    pub fn get_declaration(&self, meaning: Meaning) -> Option<Rc<dyn AstNode>> {
+        self.declarations
+            .borrow()
+            .iter()
+            .filter_map(|declaration| declaration.upgrade())
+            .find(|declaration| declaration.get_meaning() == meaning)
+    }
+}
+
 pub type Parent = RefCell<Option<Weak<dyn AstNode>>>;
 pub type Children<T> = RefCell<Vec<Rc<T>>>;
 pub type Child<T> = RefCell<Rc<T>>;
 pub type OptionalChild<T> = RefCell<Option<Rc<T>>>;
 
-pub fn bind(module: Module) -> Rc<Module> {
+/// Binds the module, collecting every binding error instead of stopping at the first one.
+pub fn bind(module: Module) -> (Rc<Module>, Vec<BindingError>) {
     let module_rc = Rc::new(module);
+    let mut errors = vec![];
 
-    module_rc.bind();
+    module_rc.bind(&mut errors);
 
-    module_rc
+    (module_rc, errors)
 }
 
 pub fn create_child<T>(node: T) -> Child<T> {
@@ -52,24 +75,51 @@ pub fn create_empty_parent() -> Parent {
     RefCell::new(None)
 }
 
+pub fn get_parent(parent: &Parent) -> Option<Rc<dyn AstNode>> {
+    parent.borrow().as_ref().and_then(|parent| parent.upgrade())
+}
+
+/// Walks from `location` up the parent chain and returns the symbol from the nearest enclosing
+/// `locals` table that declares `name` with the given meaning. Values and types live in the same
+/// tables, so a type alias `T` does not shadow an outer variable `T` and vice versa.
+pub fn resolve(name: &str, meaning: Meaning, location: &Rc<dyn AstNode>) -> Option<Symbol> {
+    let mut current = Some(Rc::clone(location));
+
+    while let Some(node) = current {
+        if let Some(locals) = node.get_locals() {
+            if let Some(symbol) = locals.borrow().get(name) {
+                if symbol.get_declaration(meaning).is_some() {
+                    return Some(symbol.clone());
+                }
+            }
+        }
+
+        current = node.get_parent();
+    }
+
+    None
+}
+
 pub fn declare_symbol(
     locals: &mut Table,
     declaration: &Rc<dyn AstNode>,
-) -> Result<(), BindingError> {
+    errors: &mut Vec<BindingError>,
+) {
     if let Some(symbol) = locals.get(&declaration.get_name()) {
         let mut declarations = symbol.declarations.borrow_mut();
-        let other = declarations.iter().find(|d| {
-            d.upgrade()
-                .map_or_else(|| false, |d| d.get_meaning() == declaration.get_meaning())
-        });
-
-        if let Some(_) = other {
-            // @todo: Would be nice to correct all of the errors, to show actually useful info
-            Err(BindingError::CannotRedeclareError)
+        let other = declarations
+            .iter()
+            .filter_map(|d| d.upgrade())
+            .find(|d| d.get_meaning() == declaration.get_meaning());
+
+        if let Some(other) = other {
+            errors.push(BindingError::CannotRedeclareError {
+                name: declaration.get_name(),
+                span: declaration.get_name_span(),
+                original_span: other.get_name_span(),
+            });
         } else {
             declarations.push(Rc::downgrade(declaration));
-
-            Ok(())
         }
     } else {
         locals.insert(
@@ -78,7 +128,107 @@ pub fn declare_symbol(
                 declarations: RefCell::new(vec![Rc::downgrade(declaration)]),
             },
         );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::parse;
+    use crate::types::{expression::Expression, statement::Statement};
+
+    fn bind_source(source: &str) -> Rc<Module> {
+        let (module, parsing_errors) = parse(Lexer::new(source));
+        assert!(parsing_errors.is_empty(), "{:?}", parsing_errors);
+        let (module, binding_errors) = bind(module);
+        assert!(binding_errors.is_empty(), "{:?}", binding_errors);
+
+        module
+    }
+
+    /// The initializer of the `var` statement at `index`.
+    fn initializer(module: &Module, index: usize) -> Rc<Expression> {
+        match &*module.statements.borrow()[index] {
+            Statement::Var { initializer, .. } => Rc::clone(&initializer.borrow()),
+            statement => panic!("Expected a var statement, found {:?}", statement),
+        }
+    }
+
+    /// The expression returned by the first statement of a function or arrow body.
+    fn returned(function: &Expression) -> Rc<dyn AstNode> {
+        let (Expression::Function { body, .. } | Expression::ArrowFunction { body, .. }) = function
+        else {
+            panic!("Expected a function, found {:?}", function);
+        };
+
+        match &*body.borrow()[0] {
+            Statement::Return { expression, .. } => {
+                Rc::clone(expression.borrow().as_ref().unwrap()) as Rc<dyn AstNode>
+            }
+            statement => panic!("Expected a return statement, found {:?}", statement),
+        }
+    }
+
+    /// Where the declaration `name` resolves to from `location` starts.
+    fn declared_at(name: &str, meaning: Meaning, location: &Rc<dyn AstNode>) -> Option<usize> {
+        resolve(name, meaning, location)
+            .and_then(|symbol| symbol.get_declaration(meaning))
+            .map(|declaration| declaration.get_span().start)
+    }
+
+    #[test]
+    fn inner_scope_shadows_outer_scope() {
+        let source = "var x = 1; var f = function (x: string) { return x };";
+        let module = bind_source(source);
+        let inner = returned(&initializer(&module, 1));
+        let outer = Rc::clone(&module) as Rc<dyn AstNode>;
+
+        assert_eq!(
+            declared_at("x", Meaning::Value, &inner),
+            source.find("x: string")
+        );
+        assert_eq!(declared_at("x", Meaning::Value, &outer), Some(0));
+    }
+
+    #[test]
+    fn values_and_types_resolve_separately() {
+        let source = "type T = number; var T = 1;";
+        let module = bind_source(source);
+        let location = Rc::clone(&module) as Rc<dyn AstNode>;
+
+        assert_eq!(declared_at("T", Meaning::Type, &location), Some(0));
+        assert_eq!(
+            declared_at("T", Meaning::Value, &location),
+            source.find("var")
+        );
+    }
+
+    #[test]
+    fn parameters_are_not_visible_outside_their_function() {
+        let source = "var f = (a: number) => a; var g = function (b: number) { return b };";
+        let module = bind_source(source);
+        let location = Rc::clone(&module) as Rc<dyn AstNode>;
+
+        assert_eq!(
+            declared_at("a", Meaning::Value, &returned(&initializer(&module, 0))),
+            source.find("a: number")
+        );
+        assert_eq!(
+            declared_at("b", Meaning::Value, &returned(&initializer(&module, 1))),
+            source.find("b: number")
+        );
+        assert!(resolve("a", Meaning::Value, &location).is_none());
+        assert!(resolve("b", Meaning::Value, &location).is_none());
+    }
+
+    #[test]
+    fn unknown_names_do_not_resolve() {
+        let module = bind_source("var x = 1;");
+        let location = Rc::clone(&module) as Rc<dyn AstNode>;
 
-        Ok(())
+        assert!(resolve("y", Meaning::Value, &location).is_none());
+        // `x` is only a value
+        assert!(resolve("x", Meaning::Type, &location).is_none());
     }
 }
@@ -0,0 +1,728 @@
+use crate::binder::{resolve, AstNode, Meaning, OptionalChild};
+use crate::errors::CheckingError;
+use crate::lexer::{Span, TokenType};
+use crate::types::{
+    expression::Expression, identifier::Identifier, module::Module, parameter::Parameter,
+    property_assignment::PropertyAssignment, property_declaration::PropertyDeclaration,
+    statement::Statement, type_node::TypeNode, type_parameter::TypeParameter,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::rc::Rc;
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Any,
+    Error,
+    Void,
+    Null,
+    Undefined,
+    Boolean,
+    Number,
+    BigInt,
+    String,
+    TypeParameter(String),
+    Object(BTreeMap<String, Type>),
+    Function {
+        parameters: Vec<(String, Type)>,
+        return_type: Box<Type>,
+    },
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::Any => write!(f, "any"),
+            Type::Error => write!(f, "error"),
+            Type::Void => write!(f, "void"),
+            Type::Null => write!(f, "null"),
+            Type::Undefined => write!(f, "undefined"),
+            Type::Boolean => write!(f, "boolean"),
+            Type::Number => write!(f, "number"),
+            Type::BigInt => write!(f, "bigint"),
+            Type::String => write!(f, "string"),
+            Type::TypeParameter(name) => write!(f, "{}", name),
+            Type::Object(members) => {
+                let members = members
+                    .iter()
+                    .map(|(name, member)| format!("{}: {}", name, member))
+                    .collect::<Vec<_>>();
+
+                if members.is_empty() {
+                    write!(f, "{{}}")
+                } else {
+                    write!(f, "{{ {} }}", members.join(", "))
+                }
+            }
+            Type::Function {
+                parameters,
+                return_type,
+            } => {
+                let parameters = parameters
+                    .iter()
+                    .map(|(name, parameter)| format!("{}: {}", name, parameter))
+                    .collect::<Vec<_>>();
+
+                write!(f, "({}) => {}", parameters.join(", "), return_type)
+            }
+        }
+    }
+}
+
+/// Type checks a module returned by `bind`, collecting every type error in it.
+pub fn check(module: &Rc<Module>) -> Vec<CheckingError> {
+    let mut checker = Checker {
+        errors: vec![],
+        expression_types: HashMap::new(),
+        type_node_types: HashMap::new(),
+    };
+
+    for statement in module.statements.borrow().iter() {
+        checker.check_statement(statement);
+    }
+
+    checker.errors
+}
+
+struct Checker {
+    errors: Vec<CheckingError>,
+    // Every node is checked once, so that its errors are reported once, no matter how many
+    // references end up asking for its type.
+    expression_types: HashMap<*const Expression, Type>,
+    type_node_types: HashMap<*const TypeNode, Type>,
+}
+
+impl Checker {
+    fn check_statement(&mut self, statement: &Rc<Statement>) {
+        match &**statement {
+            Statement::Var {
+                typename,
+                initializer,
+                ..
+            } => {
+                let initializer_type = self.check_expression(&initializer.borrow());
+
+                if let Some(typename) = typename.borrow().as_ref() {
+                    let declared_type = self.check_type(typename);
+                    self.check_assignable(
+                        &initializer_type,
+                        &declared_type,
+                        initializer.borrow().get_span(),
+                    );
+                }
+            }
+            Statement::TypeAlias { typename, .. } => {
+                self.check_type(&typename.borrow());
+            }
+            Statement::ExpressionStatement { expression, .. } => {
+                self.check_expression(&expression.borrow());
+            }
+            Statement::Return { expression, .. } => {
+                if let Some(expression) = expression.borrow().as_ref() {
+                    self.check_expression(expression);
+                }
+            }
+        }
+    }
+
+    fn check_expression(&mut self, expression: &Expression) -> Type {
+        let key = expression as *const Expression;
+        if let Some(checked) = self.expression_types.get(&key) {
+            return checked.clone();
+        }
+
+        // Placeholder for self-referencing expressions, e.g. `var x = x`
+        self.expression_types.insert(key, Type::Any);
+
+        let checked = match expression {
+            Expression::Identifier { name, .. } => self.check_identifier(&name.borrow()),
+            Expression::NumericLiteral { .. } => Type::Number,
+            Expression::BigIntLiteral { .. } => Type::BigInt,
+            Expression::StringLiteral { .. } => Type::String,
+            Expression::BooleanLiteral { .. } => Type::Boolean,
+            Expression::NullLiteral { .. } => Type::Null,
+            // There are no classes yet, so `this` is never known
+            Expression::This { .. } => Type::Any,
+            Expression::Assignment {
+                target,
+                operator,
+                value,
+                span,
+                ..
+            } => {
+                let target_type = self.check_expression(&target.borrow());
+                let value_type = self.check_expression(&value.borrow());
+
+                let is_valid_target = is_reference(&target.borrow());
+                if !is_valid_target {
+                    self.errors
+                        .push(CheckingError::InvalidAssignmentTargetError {
+                            span: target.borrow().get_span(),
+                        });
+                }
+
+                // `a += b` is checked like `a = a + b`
+                let value_type = match get_compound_operator(operator) {
+                    Some(operator) => {
+                        self.check_binary(&operator, &target_type, &value_type, *span)
+                    }
+                    None => value_type,
+                };
+                if is_valid_target {
+                    self.check_assignable(&value_type, &target_type, target.borrow().get_span());
+                }
+
+                value_type
+            }
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                span,
+                ..
+            } => {
+                let left_type = self.check_expression(&left.borrow());
+                let right_type = self.check_expression(&right.borrow());
+
+                self.check_binary(operator, &left_type, &right_type, *span)
+            }
+            Expression::Unary {
+                operator, operand, ..
+            } => {
+                let operand_type = self.check_expression(&operand.borrow());
+
+                match operator {
+                    TokenType::Exclamation | TokenType::Delete => Type::Boolean,
+                    TokenType::Typeof => Type::String,
+                    TokenType::Void => Type::Void,
+                    TokenType::Plus => Type::Number,
+                    _ => self.check_arithmetic_operand(&operand_type, operand.borrow().get_span()),
+                }
+            }
+            Expression::Update { operand, .. } => {
+                let operand_type = self.check_expression(&operand.borrow());
+
+                // `a++` assigns to `a` just like `a += 1`
+                if !is_reference(&operand.borrow()) {
+                    self.errors
+                        .push(CheckingError::InvalidAssignmentTargetError {
+                            span: operand.borrow().get_span(),
+                        });
+                }
+
+                self.check_arithmetic_operand(&operand_type, operand.borrow().get_span())
+            }
+            Expression::Parenthesized { expression, .. } => {
+                self.check_expression(&expression.borrow())
+            }
+            Expression::PropertyAccess {
+                expression, name, ..
+            } => {
+                let object_type = self.check_expression(&expression.borrow());
+                let name = name.borrow();
+
+                self.check_property(&object_type, &name.text, name.span)
+            }
+            Expression::ElementAccess {
+                expression,
+                argument,
+                ..
+            } => {
+                let object_type = self.check_expression(&expression.borrow());
+                self.check_expression(&argument.borrow());
+
+                // Only literal keys are known statically, `o["x"]` is `o.x`
+                match &**argument.borrow() {
+                    Expression::StringLiteral { value, span, .. } => {
+                        self.check_property(&object_type, value, *span)
+                    }
+                    _ => Type::Any,
+                }
+            }
+            Expression::Object { properties, .. } => {
+                let mut members = BTreeMap::new();
+                for property in properties.borrow().iter() {
+                    let value_type = self.check_expression(&property.value.borrow());
+                    members.insert(property.name.borrow().text.clone(), value_type);
+                }
+
+                Type::Object(members)
+            }
+            Expression::Function {
+                parameters,
+                typename,
+                body,
+                ..
+            } => self.check_function(&parameters.borrow(), typename, &body.borrow()),
+            Expression::ArrowFunction {
+                is_async,
+                parameters,
+                typename,
+                body,
+                ..
+            } => match self.check_function(&parameters.borrow(), typename, &body.borrow()) {
+                // Promises aren't modelled, so whatever an async arrow resolves to is unknown
+                Type::Function { parameters, .. } if *is_async => Type::Function {
+                    parameters,
+                    return_type: Box::new(Type::Any),
+                },
+                checked => checked,
+            },
+            Expression::Call {
+                expression,
+                arguments,
+                span,
+                ..
+            } => {
+                let callee_type = self.check_expression(&expression.borrow());
+                let argument_types = arguments
+                    .borrow()
+                    .iter()
+                    .map(|argument| (self.check_expression(argument), argument.get_span()))
+                    .collect::<Vec<_>>();
+
+                match callee_type {
+                    Type::Function {
+                        parameters,
+                        return_type,
+                    } => {
+                        if argument_types.len() != parameters.len() {
+                            self.errors.push(CheckingError::ArgumentCountError {
+                                span: *span,
+                                expected: parameters.len(),
+                                found: argument_types.len(),
+                            });
+                        }
+
+                        for ((argument_type, span), (_, parameter_type)) in
+                            argument_types.iter().zip(parameters.iter())
+                        {
+                            self.check_assignable(argument_type, parameter_type, *span);
+                        }
+
+                        *return_type
+                    }
+                    Type::Any | Type::Error => callee_type,
+                    _ => {
+                        self.errors.push(CheckingError::NotCallableError {
+                            span: expression.borrow().get_span(),
+                            found: callee_type,
+                        });
+
+                        Type::Error
+                    }
+                }
+            }
+        };
+
+        self.expression_types.insert(key, checked.clone());
+
+        checked
+    }
+
+    fn check_property(&mut self, object: &Type, name: &str, span: Span) -> Type {
+        match object {
+            Type::Object(members) => match members.get(name) {
+                Some(member) => member.clone(),
+                None => {
+                    self.errors.push(CheckingError::PropertyDoesNotExistError {
+                        span,
+                        name: name.to_string(),
+                        found: object.clone(),
+                    });
+
+                    Type::Error
+                }
+            },
+            // Primitives and functions have built-in members that aren't modelled here
+            _ => Type::Any,
+        }
+    }
+
+    fn check_binary(
+        &mut self,
+        operator: &TokenType,
+        left: &Type,
+        right: &Type,
+        span: Span,
+    ) -> Type {
+        match operator {
+            TokenType::AmpersandAmpersand => right.clone(),
+            // Without union types, the result is whichever side the other one fits in
+            TokenType::BarBar | TokenType::QuestionQuestion => {
+                if is_assignable_to(right, left) {
+                    left.clone()
+                } else if is_assignable_to(left, right) {
+                    right.clone()
+                } else {
+                    Type::Any
+                }
+            }
+            TokenType::EqualsEquals
+            | TokenType::ExclamationEquals
+            | TokenType::EqualsEqualsEquals
+            | TokenType::ExclamationEqualsEquals
+            | TokenType::Instanceof
+            | TokenType::In => Type::Boolean,
+            TokenType::LessThan
+            | TokenType::GreaterThan
+            | TokenType::LessThanEquals
+            | TokenType::GreaterThanEquals => {
+                let comparable = match (left, right) {
+                    (Type::Number | Type::BigInt, Type::Number | Type::BigInt) => true,
+                    (Type::String, Type::String) => true,
+                    _ => is_unknown(left) || is_unknown(right),
+                };
+                if !comparable {
+                    self.report_invalid_operands(operator, left, right, span);
+                }
+
+                Type::Boolean
+            }
+            TokenType::Plus => match (left, right) {
+                (Type::String, _) | (_, Type::String) => Type::String,
+                (Type::Number, Type::Number) => Type::Number,
+                (Type::BigInt, Type::BigInt) => Type::BigInt,
+                _ if is_unknown(left) || is_unknown(right) => Type::Any,
+                _ => {
+                    self.report_invalid_operands(operator, left, right, span);
+
+                    Type::Error
+                }
+            },
+            _ => match (left, right) {
+                // Bigints have no unsigned shift, they are never negative to begin with
+                (Type::BigInt, Type::BigInt)
+                    if *operator != TokenType::GreaterThanGreaterThanGreaterThan =>
+                {
+                    Type::BigInt
+                }
+                (Type::Number, Type::Number) => Type::Number,
+                _ if is_arithmetic(left)
+                    && is_arithmetic(right)
+                    && (is_unknown(left) || is_unknown(right)) =>
+                {
+                    Type::Number
+                }
+                _ => {
+                    self.report_invalid_operands(operator, left, right, span);
+
+                    Type::Number
+                }
+            },
+        }
+    }
+
+    fn check_arithmetic_operand(&mut self, operand: &Type, span: Span) -> Type {
+        match operand {
+            Type::BigInt => Type::BigInt,
+            _ if is_arithmetic(operand) => Type::Number,
+            _ => {
+                self.errors.push(CheckingError::ArithmeticOperandError {
+                    span,
+                    found: operand.clone(),
+                });
+
+                Type::Number
+            }
+        }
+    }
+
+    fn report_invalid_operands(
+        &mut self,
+        operator: &TokenType,
+        left: &Type,
+        right: &Type,
+        span: Span,
+    ) {
+        self.errors.push(CheckingError::InvalidOperandsError {
+            span,
+            operator: operator.clone(),
+            left: left.clone(),
+            right: right.clone(),
+        });
+    }
+
+    fn check_function(
+        &mut self,
+        parameters: &[Rc<Parameter>],
+        typename: &OptionalChild<TypeNode>,
+        body: &[Rc<Statement>],
+    ) -> Type {
+        let parameters = parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.name.borrow().text.clone(),
+                    self.check_parameter(parameter),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let declared_type = typename
+            .borrow()
+            .as_ref()
+            .map(|typename| self.check_type(typename));
+
+        let return_type = self.check_function_body(body, declared_type);
+
+        Type::Function {
+            parameters,
+            return_type: Box::new(return_type),
+        }
+    }
+
+    fn check_function_body(&mut self, body: &[Rc<Statement>], declared: Option<Type>) -> Type {
+        let mut inferred = None;
+
+        for statement in body {
+            if let Statement::Return {
+                expression, span, ..
+            } = &**statement
+            {
+                let (returned, span) = match expression.borrow().as_ref() {
+                    Some(expression) => (self.check_expression(expression), expression.get_span()),
+                    None => (Type::Void, *span),
+                };
+
+                match &declared {
+                    Some(declared) => self.check_assignable(&returned, declared, span),
+                    None => {
+                        inferred.get_or_insert(returned);
+                    }
+                }
+            } else {
+                self.check_statement(statement);
+            }
+        }
+
+        // Falling off the end of the body returns nothing, just like a bare `return`
+        declared.or(inferred).unwrap_or(Type::Void)
+    }
+
+    fn check_type(&mut self, type_node: &Rc<TypeNode>) -> Type {
+        let key = Rc::as_ptr(type_node);
+        if let Some(checked) = self.type_node_types.get(&key) {
+            return checked.clone();
+        }
+
+        // Placeholder for self-referencing aliases, e.g. `type A = A`
+        self.type_node_types.insert(key, Type::Any);
+
+        let checked = match &**type_node {
+            TypeNode::Identifier { name, .. } => {
+                let name = name.borrow();
+                match name.text.as_str() {
+                    "void" => Type::Void,
+                    "null" => Type::Null,
+                    "undefined" => Type::Undefined,
+                    "boolean" => Type::Boolean,
+                    "number" => Type::Number,
+                    "bigint" => Type::BigInt,
+                    "string" => Type::String,
+                    _ => match self.lookup(&name, Meaning::Type) {
+                        Some(declaration) => self.get_type_of_declaration(&declaration),
+                        None => {
+                            self.report_cannot_find_name(&name, Meaning::Type);
+
+                            Type::Error
+                        }
+                    },
+                }
+            }
+            TypeNode::ObjectLiteralType { properties, .. } => {
+                let mut members = BTreeMap::new();
+                for property in properties.borrow().iter() {
+                    let property_type = match property.typename.borrow().as_ref() {
+                        Some(typename) => self.check_type(typename),
+                        None => Type::Any,
+                    };
+                    members.insert(property.name.borrow().text.clone(), property_type);
+                }
+
+                Type::Object(members)
+            }
+            TypeNode::SignatureDeclaration {
+                parameters,
+                typename,
+                ..
+            } => {
+                let parameters = parameters
+                    .borrow()
+                    .iter()
+                    .map(|parameter| {
+                        (
+                            parameter.name.borrow().text.clone(),
+                            self.check_parameter(parameter),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                Type::Function {
+                    parameters,
+                    return_type: Box::new(self.check_type(&typename.borrow())),
+                }
+            }
+        };
+
+        self.type_node_types.insert(key, checked.clone());
+
+        checked
+    }
+
+    fn check_parameter(&mut self, parameter: &Parameter) -> Type {
+        match parameter.typename.borrow().as_ref() {
+            Some(typename) => self.check_type(typename),
+            None => Type::Any,
+        }
+    }
+
+    fn check_identifier(&mut self, name: &Rc<Identifier>) -> Type {
+        match self.lookup(name, Meaning::Value) {
+            Some(declaration) => self.get_type_of_declaration(&declaration),
+            // A global rather than a keyword, so a declaration can shadow it
+            None if name.text == "undefined" => Type::Undefined,
+            None => {
+                self.report_cannot_find_name(name, Meaning::Value);
+
+                Type::Error
+            }
+        }
+    }
+
+    fn get_type_of_declaration(&mut self, declaration: &Rc<dyn AstNode>) -> Type {
+        let declaration = declaration.as_any();
+
+        if let Some(statement) = declaration.downcast_ref::<Statement>() {
+            match statement {
+                Statement::Var {
+                    typename,
+                    initializer,
+                    ..
+                } => match typename.borrow().as_ref() {
+                    Some(typename) => self.check_type(typename),
+                    None => self.check_expression(&initializer.borrow()),
+                },
+                Statement::TypeAlias { typename, .. } => self.check_type(&typename.borrow()),
+                _ => Type::Any,
+            }
+        } else if let Some(expression) = declaration.downcast_ref::<Expression>() {
+            self.check_expression(expression)
+        } else if let Some(parameter) = declaration.downcast_ref::<Parameter>() {
+            self.check_parameter(parameter)
+        } else if let Some(type_parameter) = declaration.downcast_ref::<TypeParameter>() {
+            Type::TypeParameter(type_parameter.name.borrow().text.clone())
+        } else if let Some(property) = declaration.downcast_ref::<PropertyAssignment>() {
+            self.check_expression(&property.value.borrow())
+        } else if let Some(property) = declaration.downcast_ref::<PropertyDeclaration>() {
+            match property.typename.borrow().as_ref() {
+                Some(typename) => self.check_type(typename),
+                None => Type::Any,
+            }
+        } else {
+            Type::Any
+        }
+    }
+
+    fn lookup(&self, name: &Rc<Identifier>, meaning: Meaning) -> Option<Rc<dyn AstNode>> {
+        let location = Rc::clone(name) as Rc<dyn AstNode>;
+
+        resolve(&name.text, meaning, &location)?.get_declaration(meaning)
+    }
+
+    fn report_cannot_find_name(&mut self, name: &Identifier, meaning: Meaning) {
+        self.errors.push(CheckingError::CannotFindNameError {
+            span: name.span,
+            name: name.text.clone(),
+            meaning,
+        });
+    }
+
+    fn check_assignable(&mut self, source: &Type, target: &Type, span: Span) {
+        if !is_assignable_to(source, target) {
+            self.errors.push(CheckingError::TypeMismatchError {
+                span,
+                expected: target.clone(),
+                found: source.clone(),
+            });
+        }
+    }
+}
+
+/// Whether `expression` names a place that can be assigned to, e.g. `a`, `o.x` or `(o["x"])`.
+fn is_reference(expression: &Expression) -> bool {
+    match expression {
+        Expression::Identifier { .. }
+        | Expression::PropertyAccess { .. }
+        | Expression::ElementAccess { .. } => true,
+        Expression::Parenthesized { expression, .. } => is_reference(&expression.borrow()),
+        _ => false,
+    }
+}
+
+/// Types the checker knows too little about to complain, they take part in any operation.
+fn is_unknown(checked: &Type) -> bool {
+    matches!(checked, Type::Any | Type::Error | Type::TypeParameter(_))
+}
+
+fn is_arithmetic(checked: &Type) -> bool {
+    matches!(checked, Type::Number | Type::BigInt) || is_unknown(checked)
+}
+
+fn get_compound_operator(operator: &TokenType) -> Option<TokenType> {
+    match operator {
+        TokenType::PlusEquals => Some(TokenType::Plus),
+        TokenType::MinusEquals => Some(TokenType::Minus),
+        TokenType::AsteriskEquals => Some(TokenType::Asterisk),
+        TokenType::AsteriskAsteriskEquals => Some(TokenType::AsteriskAsterisk),
+        TokenType::SlashEquals => Some(TokenType::Slash),
+        TokenType::PercentEquals => Some(TokenType::Percent),
+        TokenType::LessThanLessThanEquals => Some(TokenType::LessThanLessThan),
+        TokenType::GreaterThanGreaterThanEquals => Some(TokenType::GreaterThanGreaterThan),
+        TokenType::GreaterThanGreaterThanGreaterThanEquals => {
+            Some(TokenType::GreaterThanGreaterThanGreaterThan)
+        }
+        TokenType::AmpersandEquals => Some(TokenType::Ampersand),
+        TokenType::BarEquals => Some(TokenType::Bar),
+        TokenType::CaretEquals => Some(TokenType::Caret),
+        TokenType::AmpersandAmpersandEquals => Some(TokenType::AmpersandAmpersand),
+        TokenType::BarBarEquals => Some(TokenType::BarBar),
+        TokenType::QuestionQuestionEquals => Some(TokenType::QuestionQuestion),
+        _ => None,
+    }
+}
+
+fn is_assignable_to(source: &Type, target: &Type) -> bool {
+    match (source, target) {
+        (Type::Any | Type::Error, _) | (_, Type::Any | Type::Error) => true,
+        // Type parameters are not instantiated yet, so anything goes
+        (Type::TypeParameter(_), _) | (_, Type::TypeParameter(_)) => true,
+        (Type::Object(source_members), Type::Object(target_members)) => {
+            target_members.iter().all(|(name, target_member)| {
+                source_members
+                    .get(name)
+                    .is_some_and(|source_member| is_assignable_to(source_member, target_member))
+            })
+        }
+        (
+            Type::Function {
+                parameters: source_parameters,
+                return_type: source_return_type,
+            },
+            Type::Function {
+                parameters: target_parameters,
+                return_type: target_return_type,
+            },
+        ) => {
+            source_parameters.len() <= target_parameters.len()
+                && source_parameters.iter().zip(target_parameters.iter()).all(
+                    |((_, source_parameter), (_, target_parameter))| {
+                        is_assignable_to(target_parameter, source_parameter)
+                    },
+                )
+                && is_assignable_to(source_return_type, target_return_type)
+        }
+        _ => source == target,
+    }
+}
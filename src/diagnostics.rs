@@ -0,0 +1,259 @@
+use crate::errors::{BindingError, CheckingError, ParsingError};
+use crate::lexer::Span;
+use crate::source::{FileId, SourceFile, SourceMap};
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    // The file the spans point into
+    pub file: FileId,
+    pub message: String,
+    pub span: Span,
+    pub related: Vec<RelatedInformation>,
+}
+
+/// A secondary location that explains a diagnostic, e.g. where a name was first declared.
+#[derive(Debug, Clone)]
+pub struct RelatedInformation {
+    pub message: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Diagnostic {
+    /// Renders the diagnostic the way rustc or tsc would, e.g.
+    ///
+    /// ```text
+    /// error: Type 'string' is not assignable to type 'number'.
+    ///  --> tests/complex.ts:3:1
+    ///   |
+    /// 3 | x = "haha";
+    ///   | ^
+    /// ```
+    ///
+    /// Related information follows as `note:` snippets in the same format.
+    pub fn render(&self, sources: &SourceMap) -> String {
+        let file = sources.get(self.file);
+        let mut rendered = render_snippet("error", &self.message, self.span, file);
+
+        for related in self.related.iter() {
+            rendered.push_str(&render_snippet(
+                "note",
+                &related.message,
+                related.span,
+                file,
+            ));
+        }
+
+        rendered
+    }
+}
+
+fn render_snippet(label: &str, message: &str, span: Span, file: &SourceFile) -> String {
+    let source = file.text();
+    let location = locate(source, span.start);
+    let line_start = source[..span.start]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    let line_end = source[span.start..]
+        .find('\n')
+        .map_or(source.len(), |index| span.start + index);
+    let line = source[line_start..line_end].trim_end_matches('\r');
+
+    // Keep tabs so the caret lines up with the source line
+    let padding = source[line_start..span.start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    let underline = "^".repeat(
+        source[span.start..span.end.min(line_end).max(span.start)]
+            .chars()
+            .count()
+            .max(1),
+    );
+
+    let line_number = location.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    format!(
+        "{}: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+        label,
+        message,
+        gutter,
+        file.name(),
+        location.line,
+        location.column,
+        gutter,
+        line_number,
+        line,
+        gutter,
+        padding,
+        underline
+    )
+}
+
+/// Converts a byte offset into a 1-based line and column, counting columns in characters.
+pub fn locate(source: &str, offset: usize) -> Location {
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+    Location {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+impl Diagnostic {
+    pub fn from_parsing_error(file: FileId, error: ParsingError) -> Diagnostic {
+        Diagnostic {
+            file,
+            message: error.to_string(),
+            span: error.span(),
+            related: vec![],
+        }
+    }
+
+    pub fn from_binding_error(file: FileId, error: BindingError) -> Diagnostic {
+        let related = match &error {
+            BindingError::CannotRedeclareError {
+                name,
+                original_span,
+                ..
+            } => vec![RelatedInformation {
+                message: format!("'{}' was also declared here.", name),
+                span: *original_span,
+            }],
+        };
+
+        Diagnostic {
+            file,
+            message: error.to_string(),
+            span: error.span(),
+            related,
+        }
+    }
+
+    pub fn from_checking_error(file: FileId, error: CheckingError) -> Diagnostic {
+        Diagnostic {
+            file,
+            message: error.to_string(),
+            span: error.span(),
+            related: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SourceMap;
+
+    fn render(text: &str, start: usize, end: usize) -> String {
+        let mut sources = SourceMap::new();
+        let file_id = sources.add("test.ts", text);
+        let diagnostic = Diagnostic {
+            file: file_id,
+            message: String::from("Oops."),
+            span: Span { start, end },
+            related: vec![],
+        };
+
+        diagnostic.render(&sources)
+    }
+
+    #[test]
+    fn locate_counts_columns_in_characters() {
+        let source = "var s = \"héllo\";\nvar ö = 1;";
+
+        assert_eq!(locate(source, 0), Location { line: 1, column: 1 });
+        assert_eq!(
+            locate(source, source.find(';').unwrap()),
+            Location {
+                line: 1,
+                column: 16
+            }
+        );
+        assert_eq!(
+            locate(source, source.find('=').unwrap()),
+            Location { line: 1, column: 7 }
+        );
+        assert_eq!(
+            locate(source, source.rfind('=').unwrap()),
+            Location { line: 2, column: 7 }
+        );
+    }
+
+    #[test]
+    fn locate_end_of_file() {
+        let source = "x;\n";
+
+        assert_eq!(
+            locate(source, source.len()),
+            Location { line: 2, column: 1 }
+        );
+    }
+
+    #[test]
+    fn render_underlines_multibyte_characters_once() {
+        let text = "var é = \"ü\";";
+        let start = text.find('"').unwrap();
+
+        assert_eq!(
+            render(text, start, text.len() - 1),
+            "error: Oops.\n --> test.ts:1:9\n  |\n1 | var é = \"ü\";\n  |         ^^^\n"
+        );
+    }
+
+    #[test]
+    fn render_keeps_tabs_in_the_padding() {
+        let text = "{\n\t\tx;\n}";
+        let start = text.find('x').unwrap();
+
+        assert_eq!(
+            render(text, start, start + 1),
+            "error: Oops.\n --> test.ts:2:3\n  |\n2 | \t\tx;\n  | \t\t^\n"
+        );
+    }
+
+    #[test]
+    fn render_span_at_end_of_file() {
+        let text = "var x =";
+
+        assert_eq!(
+            render(text, text.len(), text.len()),
+            "error: Oops.\n --> test.ts:1:8\n  |\n1 | var x =\n  |        ^\n"
+        );
+    }
+
+    #[test]
+    fn render_cuts_multiline_span_at_end_of_line() {
+        let text = "f({\n  a: 1\n});";
+
+        assert_eq!(
+            render(text, 2, text.len() - 1),
+            "error: Oops.\n --> test.ts:1:3\n  |\n1 | f({\n  |   ^\n"
+        );
+    }
+
+    #[test]
+    fn render_uses_the_file_of_the_diagnostic() {
+        let mut sources = SourceMap::new();
+        sources.add("a.ts", "var a = 1;");
+        let file_id = sources.add("b.ts", "var b = 2;");
+        let diagnostic = Diagnostic {
+            file: file_id,
+            message: String::from("Oops."),
+            span: Span { start: 4, end: 5 },
+            related: vec![],
+        };
+
+        assert_eq!(
+            diagnostic.render(&sources),
+            "error: Oops.\n --> b.ts:1:5\n  |\n1 | var b = 2;\n  |     ^\n"
+        );
+    }
+}
@@ -1,14 +1,282 @@
+use crate::binder::Meaning;
+use crate::checker::Type;
+use crate::lexer::{Span, Token, TokenType};
+use std::fmt;
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone)]
 pub enum LexingError {
-    UnterminatedStringLiteralError,
-    UnexpectedEndOfFileError,
+    UnterminatedStringLiteralError { span: Span },
+    UnterminatedBlockCommentError { span: Span },
+    InvalidEscapeSequenceError { span: Span },
+    InvalidNumericSeparatorError { span: Span },
+    InvalidBigIntLiteralError { span: Span },
+    IdentifierAfterNumericLiteralError { span: Span },
+    DigitExpectedError { span: Span },
+    OctalLiteralError { span: Span, digits: String },
+    LeadingZeroDecimalError { span: Span },
+    InvalidCharacterError { character: char, span: Span },
+}
+
+impl LexingError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexingError::UnterminatedStringLiteralError { span }
+            | LexingError::UnterminatedBlockCommentError { span }
+            | LexingError::InvalidEscapeSequenceError { span }
+            | LexingError::InvalidNumericSeparatorError { span }
+            | LexingError::InvalidBigIntLiteralError { span }
+            | LexingError::IdentifierAfterNumericLiteralError { span }
+            | LexingError::DigitExpectedError { span }
+            | LexingError::OctalLiteralError { span, .. }
+            | LexingError::LeadingZeroDecimalError { span }
+            | LexingError::InvalidCharacterError { span, .. } => *span,
+        }
+    }
 }
 
+impl fmt::Display for LexingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LexingError::UnterminatedStringLiteralError { .. } => {
+                write!(f, "Unterminated string literal.")
+            }
+            LexingError::UnterminatedBlockCommentError { .. } => {
+                write!(f, "Unterminated block comment, expected `*/`.")
+            }
+            LexingError::InvalidEscapeSequenceError { .. } => write!(f, "Invalid escape sequence."),
+            LexingError::InvalidNumericSeparatorError { .. } => {
+                write!(f, "Numeric separators are only allowed between digits.")
+            }
+            LexingError::InvalidBigIntLiteralError { .. } => {
+                write!(f, "A bigint literal must be an integer.")
+            }
+            LexingError::IdentifierAfterNumericLiteralError { .. } => write!(
+                f,
+                "An identifier or keyword cannot immediately follow a numeric literal."
+            ),
+            LexingError::DigitExpectedError { .. } => write!(f, "Digit expected."),
+            LexingError::OctalLiteralError { digits, .. } => write!(
+                f,
+                "Octal literals are not allowed. Use the syntax '0o{}'.",
+                digits
+            ),
+            LexingError::LeadingZeroDecimalError { .. } => {
+                write!(f, "Decimals with leading zeros are not allowed.")
+            }
+            LexingError::InvalidCharacterError { character, .. } => {
+                write!(f, "Invalid character '{}'.", character.escape_debug())
+            }
+        }
+    }
+}
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone)]
 pub enum ParsingError {
-    UnexpectedEndOfFileError,
+    UnexpectedTokenError {
+        expected: Vec<TokenType>,
+        found: Box<Token>,
+    },
+    ExpressionExpectedError {
+        found: Box<Token>,
+    },
+    UnexpectedEndOfFileError {
+        expected: Vec<TokenType>,
+        span: Span,
+    },
+    InvalidTokenError {
+        error: LexingError,
+        span: Span,
+    },
+    UnaryBeforeExponentiationError {
+        operator: TokenType,
+        span: Span,
+    },
+}
+
+impl ParsingError {
+    pub fn span(&self) -> Span {
+        match self {
+            ParsingError::UnexpectedTokenError { found, .. }
+            | ParsingError::ExpressionExpectedError { found } => found.span(),
+            ParsingError::UnexpectedEndOfFileError { span, .. }
+            | ParsingError::InvalidTokenError { span, .. }
+            | ParsingError::UnaryBeforeExponentiationError { span, .. } => *span,
+        }
+    }
+}
+
+impl fmt::Display for ParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsingError::UnexpectedTokenError { expected, found } => write!(
+                f,
+                "Expected {} but found `{}`.",
+                describe_expected(expected),
+                found.text
+            ),
+            ParsingError::ExpressionExpectedError { found } => {
+                write!(f, "Expected expression but found `{}`.", found.text)
+            }
+            ParsingError::UnexpectedEndOfFileError { expected, .. } if expected.is_empty() => {
+                write!(f, "Unexpected end of file.")
+            }
+            ParsingError::UnexpectedEndOfFileError { expected, .. } => write!(
+                f,
+                "Expected {} but reached end of file.",
+                describe_expected(expected)
+            ),
+            ParsingError::InvalidTokenError { error, .. } => write!(f, "{}", error),
+            ParsingError::UnaryBeforeExponentiationError { operator, .. } => write!(
+                f,
+                "An unary expression with the {} operator is not allowed in the left-hand side of an exponentiation expression. Consider enclosing the expression in parentheses.",
+                operator
+            ),
+        }
+    }
 }
 
+fn describe_expected(expected: &[TokenType]) -> String {
+    match expected {
+        [] => String::from("nothing"),
+        [only] => only.to_string(),
+        [rest @ .., last] => format!(
+            "{} or {}",
+            rest.iter()
+                .map(|token_type| token_type.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            last
+        ),
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum BindingError {
-    CannotRedeclareError,
+    CannotRedeclareError {
+        name: String,
+        span: Span,
+        original_span: Span,
+    },
+}
+
+impl BindingError {
+    pub fn span(&self) -> Span {
+        match self {
+            BindingError::CannotRedeclareError { span, .. } => *span,
+        }
+    }
+}
+
+impl fmt::Display for BindingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingError::CannotRedeclareError { name, .. } => {
+                write!(f, "Cannot redeclare '{}'.", name)
+            }
+        }
+    }
+}
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone)]
+pub enum CheckingError {
+    TypeMismatchError {
+        span: Span,
+        expected: Type,
+        found: Type,
+    },
+    NotCallableError {
+        span: Span,
+        found: Type,
+    },
+    ArgumentCountError {
+        span: Span,
+        expected: usize,
+        found: usize,
+    },
+    CannotFindNameError {
+        span: Span,
+        name: String,
+        meaning: Meaning,
+    },
+    InvalidOperandsError {
+        span: Span,
+        operator: TokenType,
+        left: Type,
+        right: Type,
+    },
+    ArithmeticOperandError {
+        span: Span,
+        found: Type,
+    },
+    PropertyDoesNotExistError {
+        span: Span,
+        name: String,
+        found: Type,
+    },
+    InvalidAssignmentTargetError {
+        span: Span,
+    },
+}
+
+impl CheckingError {
+    pub fn span(&self) -> Span {
+        match self {
+            CheckingError::TypeMismatchError { span, .. }
+            | CheckingError::NotCallableError { span, .. }
+            | CheckingError::ArgumentCountError { span, .. }
+            | CheckingError::CannotFindNameError { span, .. }
+            | CheckingError::InvalidOperandsError { span, .. }
+            | CheckingError::ArithmeticOperandError { span, .. }
+            | CheckingError::PropertyDoesNotExistError { span, .. }
+            | CheckingError::InvalidAssignmentTargetError { span } => *span,
+        }
+    }
+}
+
+impl fmt::Display for CheckingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckingError::TypeMismatchError {
+                expected, found, ..
+            } => write!(
+                f,
+                "Type '{}' is not assignable to type '{}'.",
+                found, expected
+            ),
+            CheckingError::NotCallableError { found, .. } => {
+                write!(f, "Type '{}' has no call signatures.", found)
+            }
+            CheckingError::ArgumentCountError {
+                expected, found, ..
+            } => write!(f, "Expected {} arguments, but got {}.", expected, found),
+            CheckingError::CannotFindNameError { name, .. } => {
+                write!(f, "Cannot find name '{}'.", name)
+            }
+            CheckingError::InvalidOperandsError {
+                operator,
+                left,
+                right,
+                ..
+            } => write!(
+                f,
+                "Operator {} cannot be applied to types '{}' and '{}'.",
+                operator, left, right
+            ),
+            CheckingError::ArithmeticOperandError { found, .. } => write!(
+                f,
+                "An arithmetic operand must be of type 'any', 'number' or 'bigint', found '{}'.",
+                found
+            ),
+            CheckingError::PropertyDoesNotExistError { name, found, .. } => {
+                write!(f, "Property '{}' does not exist on type '{}'.", name, found)
+            }
+            CheckingError::InvalidAssignmentTargetError { .. } => write!(
+                f,
+                "Invalid assignment target, it must be a variable or a property access."
+            ),
+        }
+    }
 }
@@ -1,16 +1,88 @@
 use crate::errors::LexingError;
 use phf::phf_map;
+use std::fmt;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Debug, PartialEq)]
 pub enum TokenType {
+    // Reserved words
+    Break,
+    Case,
+    Catch,
+    Class,
+    Const,
+    Continue,
+    Debugger,
+    Default,
+    Delete,
+    Do,
+    Else,
+    Enum,
+    Export,
+    Extends,
+    False,
+    Finally,
+    For,
     Function,
+    If,
+    Import,
+    In,
+    Instanceof,
+    New,
+    Null,
+    Return,
+    Super,
+    Switch,
+    This,
+    Throw,
+    True,
+    Try,
+    Typeof,
     Var,
+    Void,
+    While,
+    With,
+    // Contextual keywords, which are identifiers wherever a keyword would not make sense
+    Abstract,
+    Accessor,
+    As,
+    Asserts,
+    Async,
+    Await,
+    Constructor,
+    Declare,
+    Get,
+    Global,
+    Implements,
+    Infer,
+    Interface,
+    Is,
+    Keyof,
+    Let,
+    Module,
+    Namespace,
+    Of,
+    Out,
+    Package,
+    Private,
+    Protected,
+    Public,
+    Readonly,
+    Require,
+    Satisfies,
+    Set,
+    Static,
     Type,
-    Return,
+    Undefined,
+    Unique,
+    Yield,
     Equals,
     NumericLiteral,
+    BigIntLiteral,
     StringLiteral,
     Identifier,
+    LineComment,
+    BlockComment,
     Newline,
     Semicolon,
     Comma,
@@ -23,75 +95,529 @@ pub enum TokenType {
     CloseParen,
     LessThan,
     GreaterThan,
+    DotDotDot,
+    Dot,
+    Question,
+    QuestionDot,
+    QuestionQuestion,
+    OpenBracket,
+    CloseBracket,
+    Plus,
+    Minus,
+    Asterisk,
+    Slash,
+    Percent,
+    AsteriskAsterisk,
+    LessThanEquals,
+    GreaterThanEquals,
+    EqualsEquals,
+    ExclamationEquals,
+    EqualsEqualsEquals,
+    ExclamationEqualsEquals,
+    AmpersandAmpersand,
+    BarBar,
+    Exclamation,
+    Ampersand,
+    Bar,
+    Caret,
+    Tilde,
+    LessThanLessThan,
+    GreaterThanGreaterThan,
+    GreaterThanGreaterThanGreaterThan,
+    PlusPlus,
+    MinusMinus,
+    PlusEquals,
+    MinusEquals,
+    AsteriskEquals,
+    SlashEquals,
+    PercentEquals,
+    AsteriskAsteriskEquals,
+    LessThanLessThanEquals,
+    GreaterThanGreaterThanEquals,
+    GreaterThanGreaterThanGreaterThanEquals,
+    AmpersandEquals,
+    BarEquals,
+    CaretEquals,
+    AmpersandAmpersandEquals,
+    BarBarEquals,
+    QuestionQuestionEquals,
     Unknown,
     BOF,
     EOF,
 }
 
+impl fmt::Display for TokenType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            TokenType::NumericLiteral => "number",
+            TokenType::BigIntLiteral => "bigint",
+            TokenType::StringLiteral => "string",
+            TokenType::Identifier => "identifier",
+            TokenType::LineComment => "comment",
+            TokenType::BlockComment => "comment",
+            TokenType::Newline => "newline",
+            TokenType::Whitespace => "whitespace",
+            TokenType::Unknown => "unknown token",
+            TokenType::BOF => "beginning of file",
+            TokenType::EOF => "end of file",
+            token_type => {
+                let text = PUNCTUATION
+                    .iter()
+                    .find(|(_, punctuation)| punctuation == token_type)
+                    .map(|(text, _)| *text)
+                    .or_else(|| {
+                        KEYWORDS
+                            .entries()
+                            .find(|(_, keyword)| *keyword == token_type)
+                            .map(|(text, _)| *text)
+                    })
+                    .unwrap_or_default();
+
+                return write!(f, "`{}`", text);
+            }
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+impl TokenType {
+    /// Whether the token can be used as a name, i.e. it is an identifier or a contextual keyword,
+    /// like `type` in `var type = 1`.
+    pub fn is_identifier(&self) -> bool {
+        *self == TokenType::Identifier || self.is_contextual_keyword()
+    }
+
+    pub fn is_contextual_keyword(&self) -> bool {
+        matches!(
+            self,
+            TokenType::Abstract
+                | TokenType::Accessor
+                | TokenType::As
+                | TokenType::Asserts
+                | TokenType::Async
+                | TokenType::Await
+                | TokenType::Constructor
+                | TokenType::Declare
+                | TokenType::Get
+                | TokenType::Global
+                | TokenType::Implements
+                | TokenType::Infer
+                | TokenType::Interface
+                | TokenType::Is
+                | TokenType::Keyof
+                | TokenType::Let
+                | TokenType::Module
+                | TokenType::Namespace
+                | TokenType::Of
+                | TokenType::Out
+                | TokenType::Package
+                | TokenType::Private
+                | TokenType::Protected
+                | TokenType::Public
+                | TokenType::Readonly
+                | TokenType::Require
+                | TokenType::Satisfies
+                | TokenType::Set
+                | TokenType::Static
+                | TokenType::Type
+                | TokenType::Undefined
+                | TokenType::Unique
+                | TokenType::Yield
+        )
+    }
+
+    pub fn is_reserved_word(&self) -> bool {
+        matches!(
+            self,
+            TokenType::Break
+                | TokenType::Case
+                | TokenType::Catch
+                | TokenType::Class
+                | TokenType::Const
+                | TokenType::Continue
+                | TokenType::Debugger
+                | TokenType::Default
+                | TokenType::Delete
+                | TokenType::Do
+                | TokenType::Else
+                | TokenType::Enum
+                | TokenType::Export
+                | TokenType::Extends
+                | TokenType::False
+                | TokenType::Finally
+                | TokenType::For
+                | TokenType::Function
+                | TokenType::If
+                | TokenType::Import
+                | TokenType::In
+                | TokenType::Instanceof
+                | TokenType::New
+                | TokenType::Null
+                | TokenType::Return
+                | TokenType::Super
+                | TokenType::Switch
+                | TokenType::This
+                | TokenType::Throw
+                | TokenType::True
+                | TokenType::Try
+                | TokenType::Typeof
+                | TokenType::Var
+                | TokenType::Void
+                | TokenType::While
+                | TokenType::With
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub text: String,
+    // Cooked value, e.g. a string literal without its quotes and with escapes applied
+    pub value: String,
+    pub start: usize,
+    pub end: usize,
+    // Only filled in by `Lexer::with_trivia`. Trailing trivia runs up to and including the first
+    // line break after the token, the rest belongs to the next token.
+    pub leading_trivia: Vec<Token>,
+    pub trailing_trivia: Vec<Token>,
+    // Whether a line break comes between the previous token and this one, which decides where
+    // semicolons are inserted
+    pub preceded_by_line_break: bool,
+}
+
+impl Token {
+    pub fn span(&self) -> Span {
+        Span {
+            start: self.start,
+            end: self.end,
+        }
+    }
+
+    /// The token's text together with its trivia, exactly as it appears in the source.
+    pub fn full_text(&self) -> String {
+        let mut text = String::new();
+        for trivia in self.leading_trivia.iter() {
+            text.push_str(&trivia.text);
+        }
+        text.push_str(&self.text);
+        for trivia in self.trailing_trivia.iter() {
+            text.push_str(&trivia.text);
+        }
+
+        text
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
     pub start: usize,
     pub end: usize,
 }
 
+/// Every punctuator, longest first, so the first match is the longest one. Operators starting
+/// with `>` are only produced by `Lexer::rescan_greater_than`, since `>>` might as well close
+/// two type argument lists, as in `A<B<C>>`.
+static PUNCTUATION: &[(&str, TokenType)] = &[
+    (">>>=", TokenType::GreaterThanGreaterThanGreaterThanEquals),
+    ("...", TokenType::DotDotDot),
+    ("===", TokenType::EqualsEqualsEquals),
+    ("!==", TokenType::ExclamationEqualsEquals),
+    (">>>", TokenType::GreaterThanGreaterThanGreaterThan),
+    ("**=", TokenType::AsteriskAsteriskEquals),
+    ("<<=", TokenType::LessThanLessThanEquals),
+    (">>=", TokenType::GreaterThanGreaterThanEquals),
+    ("&&=", TokenType::AmpersandAmpersandEquals),
+    ("||=", TokenType::BarBarEquals),
+    ("??=", TokenType::QuestionQuestionEquals),
+    ("=>", TokenType::Arrow),
+    ("?.", TokenType::QuestionDot),
+    ("??", TokenType::QuestionQuestion),
+    ("**", TokenType::AsteriskAsterisk),
+    ("<=", TokenType::LessThanEquals),
+    (">=", TokenType::GreaterThanEquals),
+    ("==", TokenType::EqualsEquals),
+    ("!=", TokenType::ExclamationEquals),
+    ("&&", TokenType::AmpersandAmpersand),
+    ("||", TokenType::BarBar),
+    ("<<", TokenType::LessThanLessThan),
+    (">>", TokenType::GreaterThanGreaterThan),
+    ("++", TokenType::PlusPlus),
+    ("--", TokenType::MinusMinus),
+    ("+=", TokenType::PlusEquals),
+    ("-=", TokenType::MinusEquals),
+    ("*=", TokenType::AsteriskEquals),
+    ("/=", TokenType::SlashEquals),
+    ("%=", TokenType::PercentEquals),
+    ("&=", TokenType::AmpersandEquals),
+    ("|=", TokenType::BarEquals),
+    ("^=", TokenType::CaretEquals),
+    ("=", TokenType::Equals),
+    (";", TokenType::Semicolon),
+    (",", TokenType::Comma),
+    (":", TokenType::Colon),
+    ("{", TokenType::OpenBrace),
+    ("}", TokenType::CloseBrace),
+    ("(", TokenType::OpenParen),
+    (")", TokenType::CloseParen),
+    ("<", TokenType::LessThan),
+    (">", TokenType::GreaterThan),
+    (".", TokenType::Dot),
+    ("?", TokenType::Question),
+    ("[", TokenType::OpenBracket),
+    ("]", TokenType::CloseBracket),
+    ("+", TokenType::Plus),
+    ("-", TokenType::Minus),
+    ("*", TokenType::Asterisk),
+    ("/", TokenType::Slash),
+    ("%", TokenType::Percent),
+    ("!", TokenType::Exclamation),
+    ("&", TokenType::Ampersand),
+    ("|", TokenType::Bar),
+    ("^", TokenType::Caret),
+    ("~", TokenType::Tilde),
+];
+
+/// Primitive type names like `number` or `string` are not in here, they stay identifiers and
+/// the checker resolves them by name.
 static KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
+    "break" => TokenType::Break,
+    "case" => TokenType::Case,
+    "catch" => TokenType::Catch,
+    "class" => TokenType::Class,
+    "const" => TokenType::Const,
+    "continue" => TokenType::Continue,
+    "debugger" => TokenType::Debugger,
+    "default" => TokenType::Default,
+    "delete" => TokenType::Delete,
+    "do" => TokenType::Do,
+    "else" => TokenType::Else,
+    "enum" => TokenType::Enum,
+    "export" => TokenType::Export,
+    "extends" => TokenType::Extends,
+    "false" => TokenType::False,
+    "finally" => TokenType::Finally,
+    "for" => TokenType::For,
     "function" => TokenType::Function,
+    "if" => TokenType::If,
+    "import" => TokenType::Import,
+    "in" => TokenType::In,
+    "instanceof" => TokenType::Instanceof,
+    "new" => TokenType::New,
+    "null" => TokenType::Null,
+    "return" => TokenType::Return,
+    "super" => TokenType::Super,
+    "switch" => TokenType::Switch,
+    "this" => TokenType::This,
+    "throw" => TokenType::Throw,
+    "true" => TokenType::True,
+    "try" => TokenType::Try,
+    "typeof" => TokenType::Typeof,
     "var" => TokenType::Var,
+    "void" => TokenType::Void,
+    "while" => TokenType::While,
+    "with" => TokenType::With,
+    "abstract" => TokenType::Abstract,
+    "accessor" => TokenType::Accessor,
+    "as" => TokenType::As,
+    "asserts" => TokenType::Asserts,
+    "async" => TokenType::Async,
+    "await" => TokenType::Await,
+    "constructor" => TokenType::Constructor,
+    "declare" => TokenType::Declare,
+    "get" => TokenType::Get,
+    "global" => TokenType::Global,
+    "implements" => TokenType::Implements,
+    "infer" => TokenType::Infer,
+    "interface" => TokenType::Interface,
+    "is" => TokenType::Is,
+    "keyof" => TokenType::Keyof,
+    "let" => TokenType::Let,
+    "module" => TokenType::Module,
+    "namespace" => TokenType::Namespace,
+    "of" => TokenType::Of,
+    "out" => TokenType::Out,
+    "package" => TokenType::Package,
+    "private" => TokenType::Private,
+    "protected" => TokenType::Protected,
+    "public" => TokenType::Public,
+    "readonly" => TokenType::Readonly,
+    "require" => TokenType::Require,
+    "satisfies" => TokenType::Satisfies,
+    "set" => TokenType::Set,
+    "static" => TokenType::Static,
     "type" => TokenType::Type,
-    "return" => TokenType::Return,
+    "undefined" => TokenType::Undefined,
+    "unique" => TokenType::Unique,
+    "yield" => TokenType::Yield,
 };
 
-pub struct Lexer {
-    input: &'static str,
+/// Where the lexer is, so it can go back there after looking ahead.
+#[derive(Debug, Clone)]
+pub struct LexerState {
+    pos: usize,
+    token_start: usize,
+    previous_end: usize,
+    current: Option<Token>,
+    comments: usize,
+    errors: usize,
+}
+
+pub struct Lexer<'a> {
+    input: &'a str,
+    // Byte offset into `input`, always on a char boundary
     pos: usize,
-    current: Option<Result<Token, LexingError>>,
+    token_start: usize,
+    previous_end: usize,
+    current: Option<Token>,
+    comments: Vec<Token>,
+    errors: Vec<LexingError>,
+    preserve_trivia: bool,
 }
 
-impl Lexer {
-    pub fn new(input: &'static str) -> Lexer {
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Lexer<'a> {
         Lexer {
             input,
             pos: 0,
+            token_start: 0,
+            previous_end: 0,
             current: None,
+            comments: vec![],
+            errors: vec![],
+            preserve_trivia: false,
         }
     }
 
-    pub fn scan(&mut self) -> Option<Result<Token, LexingError>> {
-        self.skip_whitespace();
+    /// A lexer that attaches whitespace, line breaks and comments to the tokens around them, so
+    /// joining the `full_text` of every token up to EOF reproduces the input byte for byte.
+    pub fn with_trivia(input: &'a str) -> Lexer<'a> {
+        Lexer {
+            preserve_trivia: true,
+            ..Lexer::new(input)
+        }
+    }
 
-        self.current = match self.get_current_char() {
-            Some(current) => Some(self.scan_token(current)),
-            None => Some(Ok(Token {
+    /// Scans the next token. Malformed input still produces a token, e.g. `Unknown` for a stray
+    /// character, and the error is recorded for `take_errors`, so the parser never has to stop.
+    /// Once the input is exhausted, every call returns `EOF`.
+    pub fn scan(&mut self) -> Token {
+        // Not `pos`, which is past the trailing trivia of the previous token
+        self.previous_end = self.current.as_ref().map_or(self.pos, |token| token.end);
+        let leading_trivia = self.scan_trivia(false);
+
+        self.token_start = self.pos;
+        let mut token = match self.get_current_char() {
+            Some(current) => self.scan_token(current),
+            None => Token {
                 token_type: TokenType::EOF,
                 text: "".to_string(),
+                value: "".to_string(),
                 start: self.pos,
                 end: self.pos,
-            })),
+                leading_trivia: vec![],
+                trailing_trivia: vec![],
+                preceded_by_line_break: false,
+            },
         };
 
-        self.current.clone()
+        // Only trivia lies between the previous token and this one
+        token.preceded_by_line_break =
+            self.input[self.previous_end..self.token_start].contains(is_line_break);
+
+        if self.preserve_trivia {
+            token.leading_trivia = leading_trivia;
+            if token.token_type != TokenType::EOF {
+                token.trailing_trivia = self.scan_trivia(true);
+            }
+        }
+        self.current = Some(token.clone());
+
+        token
+    }
+
+    pub fn save_state(&self) -> LexerState {
+        LexerState {
+            pos: self.pos,
+            token_start: self.token_start,
+            previous_end: self.previous_end,
+            current: self.current.clone(),
+            comments: self.comments.len(),
+            errors: self.errors.len(),
+        }
+    }
+
+    /// Rewinds to a state saved earlier, forgetting comments and errors found since.
+    pub fn restore_state(&mut self, state: LexerState) {
+        self.pos = state.pos;
+        self.token_start = state.token_start;
+        self.previous_end = state.previous_end;
+        self.current = state.current;
+        self.comments.truncate(state.comments);
+        self.errors.truncate(state.errors);
+    }
+
+    /// Lexing errors found since the last call, in source order.
+    pub fn take_errors(&mut self) -> Vec<LexingError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Every comment skipped so far, in source order.
+    pub fn get_comments(&self) -> &[Token] {
+        &self.comments
+    }
+
+    pub fn take_comments(&mut self) -> Vec<Token> {
+        std::mem::take(&mut self.comments)
+    }
+
+    pub fn get_current(&self) -> Option<&Token> {
+        self.current.as_ref()
     }
 
     pub fn get(&self) -> Option<Token> {
-        match self.current {
-            Some(Ok(ref token)) => Some(token.clone()),
-            _ => None,
+        self.current.clone()
+    }
+
+    /// Start of the current token, where the node being parsed begins.
+    pub fn get_token_start(&self) -> usize {
+        self.token_start
+    }
+
+    pub fn get_token_span(&self) -> Span {
+        Span {
+            start: self.token_start,
+            end: self.pos,
         }
     }
 
-    pub fn get_type(&self) -> Option<&TokenType> {
-        match self.current {
-            Some(Ok(ref token)) => Some(&token.token_type),
-            _ => None,
+    /// Span from `start` to the end of the last consumed token.
+    pub fn get_span_from(&self, start: usize) -> Span {
+        Span {
+            start,
+            end: self.previous_end,
         }
     }
 
-    fn scan_token(&mut self, current: char) -> Result<Token, LexingError> {
-        if current == '"' {
-            self.scan_string_literal()
-        } else if current.is_digit(10) {
+    /// Span from the byte offset `start` up to where the lexer is now.
+    fn get_span_from_pos(&self, start: usize) -> Span {
+        Span {
+            start,
+            end: self.pos,
+        }
+    }
+
+    pub fn get_type(&self) -> Option<&TokenType> {
+        self.current.as_ref().map(|token| &token.token_type)
+    }
+
+    fn scan_token(&mut self, current: char) -> Token {
+        if current == '"' || current == '\'' {
+            self.scan_string_literal(current)
+        } else if current.is_ascii_digit()
+            || (current == '.' && self.get_next_char().is_some_and(|c| c.is_ascii_digit()))
+        {
             self.scan_numeric_literal()
         } else if current.is_alphabetic() || current == '_' {
             self.scan_identifier()
@@ -100,129 +626,1253 @@ impl Lexer {
         }
     }
 
-    fn scan_string_literal(&mut self) -> Result<Token, LexingError> {
+    fn scan_string_literal(&mut self, quote: char) -> Token {
         let start = self.pos;
-        self.pos += 1;
+        let mut value = String::new();
+        self.advance();
 
-        while let Some(current) = self.get_current_char() {
-            self.pos += 1;
-            if current == '"' {
-                break;
+        loop {
+            match self.get_current_char() {
+                Some(current) if current == quote => {
+                    self.advance();
+                    break;
+                }
+                None | Some('\n') | Some('\r') => {
+                    self.errors
+                        .push(LexingError::UnterminatedStringLiteralError {
+                            span: self.get_span_from_pos(start),
+                        });
+                    break;
+                }
+                Some('\\') => {
+                    let escape_start = self.pos;
+                    self.advance();
+                    match self.scan_escape_sequence(escape_start) {
+                        Ok(Some(escaped)) => value.push(escaped),
+                        Ok(None) => {}
+                        Err(error) => self.errors.push(error),
+                    }
+                }
+                Some(current) => {
+                    value.push(current);
+                    self.advance();
+                }
             }
         }
 
-        if let None = self.get_current_char() {
-            Err(LexingError::UnterminatedStringLiteralError)
-        } else {
-            Ok(Token {
-                token_type: TokenType::StringLiteral,
-                text: self.input[start..self.pos].to_string(),
-                start,
-                end: self.pos,
-            })
+        Token {
+            token_type: TokenType::StringLiteral,
+            text: self.input[start..self.pos].to_string(),
+            value,
+            start,
+            end: self.pos,
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
+            preceded_by_line_break: false,
+        }
+    }
+
+    /// Scans the escape sequence after a `\`. Returns `None` for a line continuation, which
+    /// contributes nothing to the value.
+    fn scan_escape_sequence(&mut self, start: usize) -> Result<Option<char>, LexingError> {
+        let current = match self.get_current_char() {
+            Some(current) => current,
+            // Leaves the unterminated literal to the caller
+            None => return Ok(None),
+        };
+        self.advance();
+
+        let escaped = match current {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'v' => '\u{b}',
+            '0' if !self.get_current_char().is_some_and(|c| c.is_ascii_digit()) => '\0',
+            // Legacy octal escapes such as `\1` or `\01` are not allowed in strict mode
+            '0'..='7' => {
+                return Err(LexingError::InvalidEscapeSequenceError {
+                    span: self.get_span_from_pos(start),
+                })
+            }
+            'x' => return self.scan_hex_digits(2, start).map(Some),
+            'u' if self.get_current_char() == Some('{') => {
+                self.advance();
+                let digits_start = self.pos;
+                while self
+                    .get_current_char()
+                    .is_some_and(|c| c.is_ascii_hexdigit())
+                {
+                    self.advance();
+                }
+                let digits = &self.input[digits_start..self.pos];
+
+                if digits.is_empty() || self.get_current_char() != Some('}') {
+                    return Err(LexingError::InvalidEscapeSequenceError {
+                        span: self.get_span_from_pos(start),
+                    });
+                }
+                let value = u32::from_str_radix(digits, 16).ok();
+                self.advance();
+
+                return value.and_then(char::from_u32).map(Some).ok_or(
+                    LexingError::InvalidEscapeSequenceError {
+                        span: self.get_span_from_pos(start),
+                    },
+                );
+            }
+            'u' => return self.scan_hex_digits(4, start).map(Some),
+            '\r' => {
+                if self.get_current_char() == Some('\n') {
+                    self.advance();
+                }
+                return Ok(None);
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => return Ok(None),
+            // Any other character, including quotes and `\\`, stands for itself
+            other => other,
+        };
+
+        Ok(Some(escaped))
+    }
+
+    fn scan_hex_digits(&mut self, count: usize, start: usize) -> Result<char, LexingError> {
+        let digits_start = self.pos;
+        for _ in 0..count {
+            match self.get_current_char() {
+                Some(current) if current.is_ascii_hexdigit() => self.advance(),
+                _ => {
+                    return Err(LexingError::InvalidEscapeSequenceError {
+                        span: self.get_span_from_pos(start),
+                    })
+                }
+            }
         }
+
+        u32::from_str_radix(&self.input[digits_start..self.pos], 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or(LexingError::InvalidEscapeSequenceError {
+                span: self.get_span_from_pos(start),
+            })
     }
 
-    fn scan_numeric_literal(&mut self) -> Result<Token, LexingError> {
+    /// Scans a number or bigint literal. The token value is the literal in plain decimal, e.g.
+    /// `0xFF` has the value `255`, so the parser does not need to know about radixes.
+    fn scan_numeric_literal(&mut self) -> Token {
         let start = self.pos;
-        self.pos += 1;
+        let mut error = None;
 
-        while let Some(current) = self.get_current_char() {
-            if !current.is_digit(10) {
-                break;
+        let radix = match (self.get_current_char(), self.get_next_char()) {
+            (Some('0'), Some('x' | 'X')) => Some(16),
+            (Some('0'), Some('o' | 'O')) => Some(8),
+            (Some('0'), Some('b' | 'B')) => Some(2),
+            _ => None,
+        };
+
+        let (token_type, value) = if let Some(radix) = radix {
+            self.advance();
+            self.advance();
+
+            let digits = self.scan_digits(radix, &mut error);
+            if digits.is_empty() {
+                error.get_or_insert(LexingError::DigitExpectedError {
+                    span: self.get_span_from_pos(self.pos),
+                });
+            }
+
+            if self.get_current_char() == Some('n') {
+                self.advance();
+                (TokenType::BigIntLiteral, to_decimal(&digits, radix))
+            } else {
+                let value = digits.chars().fold(0.0, |value, digit| {
+                    value * radix as f64 + digit.to_digit(radix).unwrap_or(0) as f64
+                });
+                (TokenType::NumericLiteral, value.to_string())
+            }
+        } else {
+            let mut digits = self.scan_digits(10, &mut error);
+            let mut is_integer = true;
+
+            // `017` is a legacy octal literal and `08` a decimal with a leading zero, strict
+            // mode forbids both
+            if digits.len() > 1 && digits.starts_with('0') {
+                let span = self.get_span_from_pos(start);
+                if digits.chars().all(|digit| digit < '8') {
+                    let octal = digits.trim_start_matches('0');
+                    error.get_or_insert(LexingError::OctalLiteralError {
+                        span,
+                        digits: String::from(if octal.is_empty() { "0" } else { octal }),
+                    });
+                } else {
+                    error.get_or_insert(LexingError::LeadingZeroDecimalError { span });
+                }
+            }
+
+            if self.get_current_char() == Some('.') {
+                is_integer = false;
+                self.advance();
+                digits.push('.');
+                digits.push_str(&self.scan_digits(10, &mut error));
+            }
+
+            if let Some('e' | 'E') = self.get_current_char() {
+                is_integer = false;
+                self.advance();
+                digits.push('e');
+
+                if let Some(sign @ ('+' | '-')) = self.get_current_char() {
+                    self.advance();
+                    digits.push(sign);
+                }
+
+                let exponent = self.scan_digits(10, &mut error);
+                if exponent.is_empty() {
+                    error.get_or_insert(LexingError::DigitExpectedError {
+                        span: self.get_span_from_pos(self.pos),
+                    });
+                }
+                digits.push_str(&exponent);
+            }
+
+            if self.get_current_char() == Some('n') {
+                self.advance();
+                if !is_integer {
+                    error.get_or_insert(LexingError::InvalidBigIntLiteralError {
+                        span: self.get_span_from_pos(start),
+                    });
+                }
+
+                (TokenType::BigIntLiteral, to_decimal(&digits, 10))
+            } else {
+                // `1.` and `.5` are fine for `f64::from_str`, so this only fails on bad input
+                // that was already reported above
+                let value = digits.parse::<f64>().unwrap_or(f64::NAN);
+                (TokenType::NumericLiteral, value.to_string())
+            }
+        };
+
+        // `3in` or `0b12` is one malformed literal rather than a number and an identifier
+        if self
+            .get_current_char()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        {
+            let identifier_start = self.pos;
+            while self
+                .get_current_char()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+            {
+                self.advance();
             }
+            error.get_or_insert(LexingError::IdentifierAfterNumericLiteralError {
+                span: self.get_span_from_pos(identifier_start),
+            });
+        }
 
-            self.pos += 1;
+        // Only the first problem is reported, e.g. `1__0` is one error rather than two
+        if let Some(error) = error {
+            self.errors.push(error);
         }
 
-        Ok(Token {
-            token_type: TokenType::NumericLiteral,
+        Token {
+            token_type,
             text: self.input[start..self.pos].to_string(),
+            value,
             start,
             end: self.pos,
-        })
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
+            preceded_by_line_break: false,
+        }
     }
 
-    fn scan_identifier(&mut self) -> Result<Token, LexingError> {
+    /// Scans digits in the given radix, dropping `_` separators. A separator is only allowed
+    /// between two digits.
+    fn scan_digits(&mut self, radix: u32, error: &mut Option<LexingError>) -> String {
+        let mut digits = String::new();
+
+        while let Some(current) = self.get_current_char() {
+            if current.is_digit(radix) {
+                digits.push(current);
+            } else if current == '_' {
+                let followed_by_digit = self.get_next_char().is_some_and(|c| c.is_digit(radix));
+                if digits.is_empty() || self.input[..self.pos].ends_with('_') || !followed_by_digit
+                {
+                    error.get_or_insert(LexingError::InvalidNumericSeparatorError {
+                        span: Span {
+                            start: self.pos,
+                            end: self.pos + 1,
+                        },
+                    });
+                }
+            } else {
+                break;
+            }
+
+            self.advance();
+        }
+
+        digits
+    }
+
+    fn scan_identifier(&mut self) -> Token {
         let start = self.pos;
-        self.pos += 1;
+        self.advance();
 
         while let Some(current) = self.get_current_char() {
             if !current.is_alphanumeric() && current != '_' {
                 break;
             }
-            self.pos += 1;
+            self.advance();
         }
 
         let text = &self.input[start..self.pos];
         let token_type = KEYWORDS.get(text).cloned().unwrap_or(TokenType::Identifier);
 
-        Ok(Token {
+        Token {
             token_type,
             text: text.to_string(),
+            value: text.to_string(),
             start,
             end: self.pos,
-        })
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
+            preceded_by_line_break: false,
+        }
     }
 
-    fn scan_operator(&mut self) -> Result<Token, LexingError> {
+    fn scan_operator(&mut self) -> Token {
         let start = self.pos;
-        let token = match self.get_current_char() {
-            Some('=') => match self.get_next_char() {
-                Some('>') => {
-                    self.pos += 1;
-                    Some(TokenType::Arrow)
-                }
-                _ => Some(TokenType::Equals),
-            },
-            Some(',') => Some(TokenType::Comma),
-            Some(';') => Some(TokenType::Semicolon),
-            Some(':') => Some(TokenType::Colon),
-            Some('{') => Some(TokenType::OpenBrace),
-            Some('}') => Some(TokenType::CloseBrace),
-            Some('(') => Some(TokenType::OpenParen),
-            Some(')') => Some(TokenType::CloseParen),
-            Some('<') => Some(TokenType::LessThan),
-            Some('>') => Some(TokenType::GreaterThan),
-            _ => None,
+        let rest = &self.input[start..];
+        let punctuation = PUNCTUATION.iter().find(|(text, token_type)| {
+            rest.starts_with(text)
+                && !(text.starts_with('>') && text.len() > 1)
+                // `a?.5:b` is a conditional, not an optional chain
+                && !(*token_type == TokenType::QuestionDot
+                    && rest[2..].starts_with(|c: char| c.is_ascii_digit()))
+        });
+
+        let token_type = match punctuation {
+            Some((text, token_type)) => {
+                self.pos += text.len();
+
+                token_type.clone()
+            }
+            None => {
+                let character = self.get_current_char();
+                self.advance();
+                self.errors.push(LexingError::InvalidCharacterError {
+                    character: character.unwrap_or_default(),
+                    span: self.get_span_from_pos(start),
+                });
+
+                TokenType::Unknown
+            }
         };
 
-        self.pos += 1;
+        Token {
+            token_type,
+            text: self.input[start..self.pos].to_string(),
+            value: self.input[start..self.pos].to_string(),
+            start,
+            end: self.pos,
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
+            preceded_by_line_break: false,
+        }
+    }
 
-        if let None = token {
-            Err(LexingError::UnexpectedEndOfFileError)
-        } else {
-            Ok(Token {
-                token_type: token.unwrap(),
-                text: self.input[self.pos - 1..self.pos].to_string(),
-                start,
-                end: self.pos,
-            })
+    /// Re-scans the current `>` as the longest operator starting with it, e.g. `>>=`. The parser
+    /// calls this where a binary operator is expected, while type argument lists keep the plain
+    /// `>`.
+    pub fn rescan_greater_than(&mut self) -> Token {
+        let token = match &self.current {
+            Some(token) if token.token_type == TokenType::GreaterThan => token.clone(),
+            Some(token) => return token.clone(),
+            None => return self.scan(),
+        };
+
+        let rest = &self.input[token.start..];
+        let (text, token_type) = PUNCTUATION
+            .iter()
+            .find(|(text, _)| text.starts_with('>') && rest.starts_with(text))
+            .unwrap_or(&(">", TokenType::GreaterThan));
+        if *text == ">" {
+            return token;
         }
+
+        self.pos = token.start + text.len();
+        let trailing_trivia = if self.preserve_trivia {
+            self.scan_trivia(true)
+        } else {
+            vec![]
+        };
+
+        let token = Token {
+            token_type: token_type.clone(),
+            text: text.to_string(),
+            value: text.to_string(),
+            start: token.start,
+            end: token.start + text.len(),
+            leading_trivia: token.leading_trivia,
+            trailing_trivia,
+            preceded_by_line_break: token.preceded_by_line_break,
+        };
+        self.current = Some(token.clone());
+
+        token
     }
 
     fn get_current_char(&self) -> Option<char> {
-        self.input.chars().nth(self.pos)
+        self.input[self.pos..].chars().next()
     }
 
     fn get_next_char(&self) -> Option<char> {
-        self.input.chars().nth(self.pos + 1)
+        let mut chars = self.input[self.pos..].chars();
+        chars.next();
+        chars.next()
     }
 
-    fn skip_whitespace(&mut self) {
-        self.pos += self
-            .input
-            .chars()
-            .skip(self.pos)
-            .take_while(|&c| c.is_whitespace())
-            .count();
+    fn advance(&mut self) {
+        if let Some(current) = self.get_current_char() {
+            self.pos += current.len_utf8();
+        }
     }
+
+    /// Skips whitespace, line breaks and comments, returning them when trivia is preserved.
+    /// Trailing trivia stops after the first line break.
+    fn scan_trivia(&mut self, trailing: bool) -> Vec<Token> {
+        let mut trivia = vec![];
+
+        while let Some(current) = self.get_current_char() {
+            let start = self.pos;
+            let token_type = match (current, self.get_next_char()) {
+                ('/', Some('/')) => {
+                    self.scan_line_comment();
+                    TokenType::LineComment
+                }
+                ('/', Some('*')) => {
+                    self.scan_block_comment();
+                    TokenType::BlockComment
+                }
+                ('\r', Some('\n')) => {
+                    self.advance();
+                    self.advance();
+                    TokenType::Newline
+                }
+                (current, _) if is_line_break(current) => {
+                    self.advance();
+                    TokenType::Newline
+                }
+                (current, _) if current.is_whitespace() => {
+                    while self
+                        .get_current_char()
+                        .is_some_and(|c| c.is_whitespace() && !is_line_break(c))
+                    {
+                        self.advance();
+                    }
+                    TokenType::Whitespace
+                }
+                _ => break,
+            };
+
+            if self.preserve_trivia {
+                trivia.push(Token {
+                    token_type: token_type.clone(),
+                    text: self.input[start..self.pos].to_string(),
+                    value: self.input[start..self.pos].to_string(),
+                    start,
+                    end: self.pos,
+                    leading_trivia: vec![],
+                    trailing_trivia: vec![],
+                    preceded_by_line_break: false,
+                });
+            }
+
+            if trailing && token_type == TokenType::Newline {
+                break;
+            }
+        }
+
+        trivia
+    }
+
+    fn scan_line_comment(&mut self) {
+        let start = self.pos;
+
+        while let Some(current) = self.get_current_char() {
+            if is_line_break(current) {
+                break;
+            }
+
+            self.advance();
+        }
+
+        self.push_comment(TokenType::LineComment, start);
+    }
+
+    fn scan_block_comment(&mut self) {
+        let start = self.pos;
+        self.advance();
+        self.advance();
+
+        loop {
+            match (self.get_current_char(), self.get_next_char()) {
+                (Some('*'), Some('/')) => {
+                    self.advance();
+                    self.advance();
+                    self.push_comment(TokenType::BlockComment, start);
+
+                    return;
+                }
+                (Some(_), _) => self.advance(),
+                (None, _) => {
+                    self.push_comment(TokenType::BlockComment, start);
+                    self.errors
+                        .push(LexingError::UnterminatedBlockCommentError {
+                            span: self.get_span_from_pos(start),
+                        });
+
+                    return;
+                }
+            }
+        }
+    }
+
+    fn push_comment(&mut self, token_type: TokenType, start: usize) {
+        self.comments.push(Token {
+            token_type,
+            text: self.input[start..self.pos].to_string(),
+            value: self.input[start..self.pos].to_string(),
+            start,
+            end: self.pos,
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
+            preceded_by_line_break: false,
+        });
+    }
+}
+
+fn is_line_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// Converts the digits of an integer in the given radix to decimal, without a size limit.
+fn to_decimal(digits: &str, radix: u32) -> String {
+    // Little-endian decimal digits
+    let mut decimal: Vec<u32> = vec![];
+
+    for digit in digits.chars().filter_map(|c| c.to_digit(radix)) {
+        let mut carry = digit;
+        for place in decimal.iter_mut() {
+            let value = *place * radix + carry;
+            *place = value % 10;
+            carry = value / 10;
+        }
+        while carry > 0 {
+            decimal.push(carry % 10);
+            carry /= 10;
+        }
+    }
+
+    if decimal.is_empty() {
+        return "0".to_string();
+    }
+
+    decimal
+        .iter()
+        .rev()
+        .filter_map(|digit| char::from_digit(*digit, 10))
+        .collect()
 }
 
-impl Iterator for Lexer {
-    type Item = Result<Token, LexingError>;
+impl Iterator for Lexer<'_> {
+    type Item = Token;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.scan()
+        Some(self.scan())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lexes the whole input, up to and including the EOF token.
+    fn lex(input: &str) -> (Vec<Token>, Vec<LexingError>) {
+        let mut lexer = Lexer::new(input);
+        let mut tokens = vec![];
+
+        loop {
+            let token = lexer.next().unwrap();
+            let is_eof = token.token_type == TokenType::EOF;
+            tokens.push(token);
+
+            if is_eof {
+                return (tokens, lexer.take_errors());
+            }
+        }
+    }
+
+    fn types(input: &str) -> Vec<TokenType> {
+        let (tokens, errors) = lex(input);
+        assert!(errors.is_empty(), "unexpected errors {:?}", errors);
+
+        tokens.into_iter().map(|token| token.token_type).collect()
+    }
+
+    fn single(input: &str) -> Token {
+        let (mut tokens, errors) = lex(input);
+        assert!(errors.is_empty(), "unexpected errors {:?}", errors);
+        assert_eq!(tokens.len(), 2, "expected one token in {:?}", input);
+
+        tokens.remove(0)
+    }
+
+    fn single_error(input: &str) -> LexingError {
+        let (_, mut errors) = lex(input);
+        assert_eq!(errors.len(), 1, "expected one error in {:?}", input);
+
+        errors.remove(0)
+    }
+
+    #[test]
+    fn empty_input_is_eof() {
+        let (tokens, _) = lex("");
+
+        assert_eq!(tokens.len(), 1);
+        let eof = &tokens[0];
+        assert_eq!(eof.token_type, TokenType::EOF);
+        assert_eq!(eof.span(), Span { start: 0, end: 0 });
+    }
+
+    #[test]
+    fn keeps_yielding_eof_after_the_end() {
+        let mut lexer = Lexer::new("x");
+
+        assert_eq!(lexer.next().unwrap().token_type, TokenType::Identifier);
+        for _ in 0..3 {
+            let token = lexer.next().unwrap();
+            assert_eq!(token.token_type, TokenType::EOF);
+            assert_eq!(token.span(), Span { start: 1, end: 1 });
+        }
+    }
+
+    #[test]
+    fn keeps_yielding_eof_after_an_error() {
+        let mut lexer = Lexer::new("\"open");
+
+        assert_eq!(lexer.next().unwrap().token_type, TokenType::StringLiteral);
+        assert_eq!(lexer.take_errors().len(), 1);
+        assert_eq!(lexer.next().unwrap().token_type, TokenType::EOF);
+        assert_eq!(lexer.next().unwrap().token_type, TokenType::EOF);
+        assert!(lexer.take_errors().is_empty());
+    }
+
+    #[test]
+    fn scans_a_declaration() {
+        assert_eq!(
+            types("var x: number = 1;"),
+            vec![
+                TokenType::Var,
+                TokenType::Identifier,
+                TokenType::Colon,
+                TokenType::Identifier,
+                TokenType::Equals,
+                TokenType::NumericLiteral,
+                TokenType::Semicolon,
+                TokenType::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn scans_keywords_and_identifiers() {
+        assert_eq!(
+            types("function var type return functions _x x1"),
+            vec![
+                TokenType::Function,
+                TokenType::Var,
+                TokenType::Type,
+                TokenType::Return,
+                TokenType::Identifier,
+                TokenType::Identifier,
+                TokenType::Identifier,
+                TokenType::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn scans_reserved_and_contextual_keywords() {
+        assert_eq!(
+            types("if else while true null typeof let as of undefined number"),
+            vec![
+                TokenType::If,
+                TokenType::Else,
+                TokenType::While,
+                TokenType::True,
+                TokenType::Null,
+                TokenType::Typeof,
+                TokenType::Let,
+                TokenType::As,
+                TokenType::Of,
+                TokenType::Undefined,
+                TokenType::Identifier,
+                TokenType::EOF,
+            ]
+        );
+
+        assert!(TokenType::If.is_reserved_word());
+        assert!(!TokenType::If.is_identifier());
+        assert!(TokenType::As.is_contextual_keyword());
+        assert!(TokenType::Type.is_identifier());
+        assert_eq!(TokenType::Instanceof.to_string(), "`instanceof`");
+    }
+
+    #[test]
+    fn restores_a_saved_state() {
+        let mut lexer = Lexer::new("a /* c */ @ b");
+        lexer.next();
+        let state = lexer.save_state();
+
+        assert_eq!(lexer.next().unwrap().token_type, TokenType::Unknown);
+        assert_eq!(lexer.next().unwrap().text, "b");
+        lexer.restore_state(state);
+
+        assert_eq!(lexer.get().unwrap().text, "a");
+        assert!(lexer.get_comments().is_empty());
+        assert_eq!(lexer.next().unwrap().token_type, TokenType::Unknown);
+        assert_eq!(lexer.take_errors().len(), 1);
+    }
+
+    #[test]
+    fn scans_punctuation() {
+        assert_eq!(
+            types("= => , ; : { } ( ) < >"),
+            vec![
+                TokenType::Equals,
+                TokenType::Arrow,
+                TokenType::Comma,
+                TokenType::Semicolon,
+                TokenType::Colon,
+                TokenType::OpenBrace,
+                TokenType::CloseBrace,
+                TokenType::OpenParen,
+                TokenType::CloseParen,
+                TokenType::LessThan,
+                TokenType::GreaterThan,
+                TokenType::EOF,
+            ]
+        );
+        assert_eq!(single("=>").text, "=>");
+    }
+
+    #[test]
+    fn scans_the_longest_operator() {
+        assert_eq!(
+            types("... . ?. ?? ??= ** **= === !== == != = => ++ += + -- -= - &&= && &= & ||= || |= | << <<= <= <"),
+            vec![
+                TokenType::DotDotDot,
+                TokenType::Dot,
+                TokenType::QuestionDot,
+                TokenType::QuestionQuestion,
+                TokenType::QuestionQuestionEquals,
+                TokenType::AsteriskAsterisk,
+                TokenType::AsteriskAsteriskEquals,
+                TokenType::EqualsEqualsEquals,
+                TokenType::ExclamationEqualsEquals,
+                TokenType::EqualsEquals,
+                TokenType::ExclamationEquals,
+                TokenType::Equals,
+                TokenType::Arrow,
+                TokenType::PlusPlus,
+                TokenType::PlusEquals,
+                TokenType::Plus,
+                TokenType::MinusMinus,
+                TokenType::MinusEquals,
+                TokenType::Minus,
+                TokenType::AmpersandAmpersandEquals,
+                TokenType::AmpersandAmpersand,
+                TokenType::AmpersandEquals,
+                TokenType::Ampersand,
+                TokenType::BarBarEquals,
+                TokenType::BarBar,
+                TokenType::BarEquals,
+                TokenType::Bar,
+                TokenType::LessThanLessThan,
+                TokenType::LessThanLessThanEquals,
+                TokenType::LessThanEquals,
+                TokenType::LessThan,
+                TokenType::EOF,
+            ]
+        );
+        assert_eq!(
+            types("a[0]!~b^c%d/e"),
+            vec![
+                TokenType::Identifier,
+                TokenType::OpenBracket,
+                TokenType::NumericLiteral,
+                TokenType::CloseBracket,
+                TokenType::Exclamation,
+                TokenType::Tilde,
+                TokenType::Identifier,
+                TokenType::Caret,
+                TokenType::Identifier,
+                TokenType::Percent,
+                TokenType::Identifier,
+                TokenType::Slash,
+                TokenType::Identifier,
+                TokenType::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn question_dot_before_a_digit_is_a_conditional() {
+        assert_eq!(
+            types("a?.5:b"),
+            vec![
+                TokenType::Identifier,
+                TokenType::Question,
+                TokenType::NumericLiteral,
+                TokenType::Colon,
+                TokenType::Identifier,
+                TokenType::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn scans_greater_than_alone_until_rescanned() {
+        assert_eq!(
+            types("A<B<C>>"),
+            vec![
+                TokenType::Identifier,
+                TokenType::LessThan,
+                TokenType::Identifier,
+                TokenType::LessThan,
+                TokenType::Identifier,
+                TokenType::GreaterThan,
+                TokenType::GreaterThan,
+                TokenType::EOF,
+            ]
+        );
+
+        for (input, token_type) in [
+            (">", TokenType::GreaterThan),
+            (">=", TokenType::GreaterThanEquals),
+            (">>", TokenType::GreaterThanGreaterThan),
+            (">>=", TokenType::GreaterThanGreaterThanEquals),
+            (">>>", TokenType::GreaterThanGreaterThanGreaterThan),
+            (">>>=", TokenType::GreaterThanGreaterThanGreaterThanEquals),
+        ] {
+            let mut lexer = Lexer::new(input);
+            lexer.next();
+
+            let token = lexer.rescan_greater_than();
+            assert_eq!(token.token_type, token_type);
+            assert_eq!(token.text, input);
+            assert_eq!(lexer.next().unwrap().token_type, TokenType::EOF);
+        }
+    }
+
+    #[test]
+    fn rescan_leaves_other_tokens_alone() {
+        let mut lexer = Lexer::new("< >");
+        lexer.next();
+
+        assert_eq!(lexer.rescan_greater_than().token_type, TokenType::LessThan);
+        assert_eq!(lexer.next().unwrap().token_type, TokenType::GreaterThan);
+    }
+
+    #[test]
+    fn describes_punctuation() {
+        assert_eq!(TokenType::QuestionQuestionEquals.to_string(), "`??=`");
+        assert_eq!(TokenType::CloseParen.to_string(), "`)`");
+        assert_eq!(TokenType::Identifier.to_string(), "identifier");
+    }
+
+    fn round_trip(input: &str) -> String {
+        let mut lexer = Lexer::with_trivia(input);
+        let mut text = String::new();
+
+        loop {
+            let token = lexer.next().unwrap();
+            text.push_str(&token.full_text());
+
+            if token.token_type == TokenType::EOF {
+                return text;
+            }
+        }
+    }
+
+    #[test]
+    fn trivia_round_trips_the_source() {
+        let inputs = [
+            "",
+            "   ",
+            "var x = 1; // one\n\n/* two */ x = 2;\n",
+            "a\r\n\tb \u{2028} c",
+            "f<A<B>>=>{ } /* open",
+            "'open\n\"ok\" @ € 1__0",
+            include_str!("../tests/complex.ts"),
+            include_str!("../tests/double_call.ts"),
+        ];
+
+        for input in inputs {
+            assert_eq!(round_trip(input), input);
+        }
+    }
+
+    #[test]
+    fn attaches_trivia_up_to_the_line_break_as_trailing() {
+        let mut lexer = Lexer::with_trivia("a  // c\n  b");
+
+        let a = lexer.next().unwrap();
+        let trailing = a
+            .trailing_trivia
+            .iter()
+            .map(|trivia| trivia.token_type.clone())
+            .collect::<Vec<_>>();
+        assert!(a.leading_trivia.is_empty());
+        assert_eq!(
+            trailing,
+            vec![
+                TokenType::Whitespace,
+                TokenType::LineComment,
+                TokenType::Newline
+            ]
+        );
+
+        let b = lexer.next().unwrap();
+        assert_eq!(b.leading_trivia.len(), 1);
+        assert_eq!(b.leading_trivia[0].text, "  ");
+        assert!(b.trailing_trivia.is_empty());
+    }
+
+    #[test]
+    fn keeps_trivia_before_eof_on_the_eof_token() {
+        let mut lexer = Lexer::with_trivia("a\n\n// end");
+        lexer.next();
+
+        let eof = lexer.next().unwrap();
+        assert_eq!(eof.token_type, TokenType::EOF);
+        assert_eq!(eof.full_text(), "\n// end");
+    }
+
+    #[test]
+    fn spans_exclude_trivia() {
+        let mut lexer = Lexer::with_trivia("ab  \ncd");
+        lexer.next();
+        lexer.next();
+
+        assert_eq!(lexer.get_token_start(), 5);
+        assert_eq!(lexer.get_span_from(0), Span { start: 0, end: 2 });
+    }
+
+    #[test]
+    fn rescan_keeps_the_trivia() {
+        let mut lexer = Lexer::with_trivia(" >>= x");
+        lexer.next();
+
+        let token = lexer.rescan_greater_than();
+        assert_eq!(token.full_text(), " >>= ");
+        assert_eq!(lexer.next().unwrap().full_text(), "x");
+    }
+
+    #[test]
+    fn drops_trivia_by_default() {
+        let (tokens, _) = lex(" a // c\n b ");
+
+        assert!(tokens
+            .iter()
+            .all(|token| token.leading_trivia.is_empty() && token.trailing_trivia.is_empty()));
+    }
+
+    #[test]
+    fn tracks_preceding_line_breaks() {
+        let (tokens, _) = lex("a b\nc /* \n */ d /* */ e\r\nf");
+        let preceded = tokens
+            .iter()
+            .map(|token| token.preceded_by_line_break)
+            .collect::<Vec<_>>();
+
+        assert_eq!(preceded, vec![false, false, true, true, false, true, false]);
+    }
+
+    #[test]
+    fn tracks_byte_spans() {
+        let (tokens, _) = lex("  héllo = 1");
+        let identifier = &tokens[0];
+
+        assert_eq!(identifier.text, "héllo");
+        assert_eq!(identifier.span(), Span { start: 2, end: 8 });
+        assert_eq!(tokens[1].start, 9);
+    }
+
+    #[test]
+    fn tracks_the_previous_token_end() {
+        let mut lexer = Lexer::new("ab  cd");
+        lexer.next();
+        lexer.next();
+
+        assert_eq!(lexer.get_token_start(), 4);
+        assert_eq!(lexer.get_span_from(0), Span { start: 0, end: 2 });
+    }
+
+    #[test]
+    fn skips_and_keeps_comments() {
+        let mut lexer = Lexer::new("a // line\n/* block\n */ b");
+
+        assert_eq!(lexer.next().unwrap().text, "a");
+        assert_eq!(lexer.next().unwrap().text, "b");
+
+        let comments = lexer.get_comments();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].token_type, TokenType::LineComment);
+        assert_eq!(comments[0].text, "// line");
+        assert_eq!(comments[1].token_type, TokenType::BlockComment);
+        assert_eq!(comments[1].text, "/* block\n */");
+    }
+
+    #[test]
+    fn comment_at_end_of_file() {
+        assert_eq!(
+            types("x // done"),
+            vec![TokenType::Identifier, TokenType::EOF]
+        );
+        assert_eq!(types("x /**/"), vec![TokenType::Identifier, TokenType::EOF]);
+    }
+
+    #[test]
+    fn reports_unterminated_block_comment() {
+        let error = single_error("x /* open");
+
+        assert!(matches!(
+            error,
+            LexingError::UnterminatedBlockCommentError { .. }
+        ));
+        assert_eq!(error.span(), Span { start: 2, end: 9 });
+    }
+
+    #[test]
+    fn scans_string_at_end_of_file() {
+        let token = single("\"done\"");
+
+        assert_eq!(token.token_type, TokenType::StringLiteral);
+        assert_eq!(token.text, "\"done\"");
+        assert_eq!(token.value, "done");
+    }
+
+    #[test]
+    fn scans_single_quoted_strings() {
+        let token = single("'it\\'s \"quoted\"'");
+
+        assert_eq!(token.value, "it's \"quoted\"");
+    }
+
+    #[test]
+    fn cooks_escape_sequences() {
+        assert_eq!(single(r#""a\nb\tc\\d\"e""#).value, "a\nb\tc\\d\"e");
+        assert_eq!(single(r#""\x41B\u{1F600}""#).value, "AB\u{1F600}");
+        assert_eq!(single("\"a\\\nb\"").value, "ab");
+        assert_eq!(single(r#""\q""#).value, "q");
+    }
+
+    #[test]
+    fn reports_invalid_escape_sequences() {
+        for input in [
+            r#""\xZZ""#,
+            r#""\u12""#,
+            r#""\u{}""#,
+            r#""\u{110000}""#,
+            r#""\01""#,
+            r#""\1""#,
+            r#""\7""#,
+        ] {
+            let error = single_error(input);
+
+            assert!(
+                matches!(error, LexingError::InvalidEscapeSequenceError { .. }),
+                "{:?}",
+                input
+            );
+            assert_eq!(error.span().start, 1);
+        }
+    }
+
+    #[test]
+    fn resumes_after_invalid_escape_sequence() {
+        let (tokens, errors) = lex(r#""\xZZ" x"#);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(tokens[0].token_type, TokenType::StringLiteral);
+        assert_eq!(tokens[1].text, "x");
+    }
+
+    #[test]
+    fn reports_unterminated_strings() {
+        for input in ["\"open", "'open", "\"open\nx", "\"open\\"] {
+            let error = single_error(input);
+
+            assert!(
+                matches!(error, LexingError::UnterminatedStringLiteralError { .. }),
+                "{:?}",
+                input
+            );
+            assert_eq!(error.span().start, 0);
+        }
+    }
+
+    #[test]
+    fn scans_numeric_literals() {
+        let cases = [
+            ("1", "1"),
+            ("1.5", "1.5"),
+            (".5", "0.5"),
+            ("1.", "1"),
+            ("1e3", "1000"),
+            ("2.5E-1", "0.25"),
+            ("0xFF", "255"),
+            ("0o17", "15"),
+            ("0b101", "5"),
+            ("1_000_000", "1000000"),
+        ];
+
+        for (input, value) in cases {
+            let token = single(input);
+            assert_eq!(token.token_type, TokenType::NumericLiteral, "{:?}", input);
+            assert_eq!(token.text, input);
+            assert_eq!(token.value, value, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn scans_large_numbers_without_panicking() {
+        let token = single("99999999999999999999999999");
+
+        assert_eq!(token.value.parse::<f64>().unwrap(), 1e26);
+    }
+
+    #[test]
+    fn scans_bigint_literals() {
+        let cases = [
+            ("123n", "123"),
+            ("0n", "0"),
+            ("0xFFn", "255"),
+            ("1_0n", "10"),
+            ("0xFFFFFFFFFFFFFFFFFFFFn", "1208925819614629174706175"),
+        ];
+
+        for (input, value) in cases {
+            let token = single(input);
+            assert_eq!(token.token_type, TokenType::BigIntLiteral, "{:?}", input);
+            assert_eq!(token.value, value, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn reports_malformed_numeric_literals() {
+        assert!(matches!(
+            single_error("1__0"),
+            LexingError::InvalidNumericSeparatorError { .. }
+        ));
+        assert!(matches!(
+            single_error("1_"),
+            LexingError::InvalidNumericSeparatorError { .. }
+        ));
+        assert!(matches!(
+            single_error("0x_F"),
+            LexingError::InvalidNumericSeparatorError { .. }
+        ));
+        assert!(matches!(
+            single_error("1.5n"),
+            LexingError::InvalidBigIntLiteralError { .. }
+        ));
+        assert!(matches!(
+            single_error("0x"),
+            LexingError::DigitExpectedError { .. }
+        ));
+        assert!(matches!(
+            single_error("1e+"),
+            LexingError::DigitExpectedError { .. }
+        ));
+
+        let error = single_error("3in");
+        assert!(matches!(
+            error,
+            LexingError::IdentifierAfterNumericLiteralError { .. }
+        ));
+        assert_eq!(error.span(), Span { start: 1, end: 3 });
+    }
+
+    #[test]
+    fn reports_leading_zeros() {
+        let error = single_error("017");
+        assert!(matches!(error, LexingError::OctalLiteralError { .. }));
+        assert_eq!(error.span(), Span { start: 0, end: 3 });
+        assert_eq!(
+            error.to_string(),
+            "Octal literals are not allowed. Use the syntax '0o17'."
+        );
+
+        assert_eq!(
+            single_error("00").to_string(),
+            "Octal literals are not allowed. Use the syntax '0o0'."
+        );
+
+        for input in ["08", "019", "08.5"] {
+            assert!(
+                matches!(
+                    single_error(input),
+                    LexingError::LeadingZeroDecimalError { .. }
+                ),
+                "{:?}",
+                input
+            );
+        }
+
+        for input in ["0", "0.5", "0e1", "0n", "10"] {
+            single(input);
+        }
+    }
+
+    #[test]
+    fn reports_invalid_characters_with_position() {
+        let (tokens, errors) = lex("x @ y");
+
+        assert_eq!(tokens[1].token_type, TokenType::Unknown);
+        assert_eq!(tokens[1].text, "@");
+        assert_eq!(tokens[2].text, "y");
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            LexingError::InvalidCharacterError { character: '@', .. }
+        ));
+        assert_eq!(errors[0].span(), Span { start: 2, end: 3 });
+        assert_eq!(errors[0].to_string(), "Invalid character '@'.");
+    }
+
+    #[test]
+    fn scans_multibyte_unknown_characters_whole() {
+        let (tokens, errors) = lex("x € y");
+
+        assert_eq!(tokens[1].token_type, TokenType::Unknown);
+        assert_eq!(tokens[1].text, "€");
+        assert_eq!(errors[0].span(), Span { start: 2, end: 5 });
+    }
+
+    #[test]
+    fn keeps_the_token_of_a_malformed_literal() {
+        let (tokens, errors) = lex("1__0 'open");
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(tokens[0].token_type, TokenType::NumericLiteral);
+        assert_eq!(tokens[0].text, "1__0");
+        assert_eq!(tokens[1].token_type, TokenType::StringLiteral);
+        assert_eq!(tokens[1].value, "open");
     }
 }
@@ -0,0 +1,55 @@
+//! A TypeScript typechecker for a small subset of the language, after
+//! [mini-typescript](https://github.com/sandersn/mini-typescript).
+//!
+//! Checking a file goes through the same phases as in `tsc`:
+//!
+//! 1. [`Lexer`] turns the source text into tokens,
+//! 2. [`parse`] builds the [`types::module::Module`] AST from them,
+//! 3. [`bind`] links every node to its parent and fills the symbol tables,
+//! 4. [`check`] walks the bound module and reports type errors.
+//!
+//! Every phase collects its errors instead of stopping at the first one, and all of them
+//! convert into a [`Diagnostic`] that remembers its file and renders against the
+//! [`SourceMap`] that loaded it. [`run_checker`] runs the whole pipeline at once on a file.
+
+pub mod binder;
+pub mod checker;
+pub mod diagnostics;
+pub mod errors;
+pub mod lexer;
+pub mod parser;
+pub mod source;
+pub mod types;
+
+pub use crate::binder::bind;
+pub use crate::checker::check;
+pub use crate::diagnostics::Diagnostic;
+pub use crate::lexer::Lexer;
+pub use crate::parser::parse;
+pub use crate::source::{SourceFile, SourceMap};
+
+/// Lexes, parses, binds and checks `file`, returning the diagnostics of every phase in that
+/// order.
+pub fn run_checker(file: &SourceFile) -> Vec<Diagnostic> {
+    let file_id = file.id();
+    let lexer = Lexer::new(file.text());
+    let (ast, parsing_errors) = parse(lexer);
+    let (binded_ast, binding_errors) = bind(ast);
+
+    let mut diagnostics = parsing_errors
+        .into_iter()
+        .map(|error| Diagnostic::from_parsing_error(file_id, error))
+        .collect::<Vec<_>>();
+    diagnostics.extend(
+        binding_errors
+            .into_iter()
+            .map(|error| Diagnostic::from_binding_error(file_id, error)),
+    );
+    diagnostics.extend(
+        check(&binded_ast)
+            .into_iter()
+            .map(|error| Diagnostic::from_checking_error(file_id, error)),
+    );
+
+    diagnostics
+}
@@ -1,14 +1,7 @@
-use crate::binder::bind;
-use crate::lexer::Lexer;
-use crate::parser::parse;
+use mini_compiler::{run_checker, SourceMap};
 use std::env;
 use std::fs;
-
-mod binder;
-mod errors;
-mod lexer;
-mod parser;
-mod types;
+use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -18,18 +11,21 @@ fn main() {
 
     match contents {
         Ok(contents) => {
-            run_checker(contents);
+            let mut sources = SourceMap::new();
+            let file_id = sources.add(file_path, contents);
+            let file = sources.get(file_id);
+            let diagnostics = run_checker(file);
+
+            for diagnostic in diagnostics.iter() {
+                println!("{}", diagnostic.render(&sources));
+            }
+
+            if !diagnostics.is_empty() {
+                process::exit(1);
+            }
         }
         Err(error) => {
             println!("Error: {:?}", error);
         }
     }
 }
-
-pub fn run_checker(contents: String) {
-    let mut lexer = Lexer::new(contents.leak());
-    let ast = parse(&mut lexer);
-    let binded_ast = bind(ast.unwrap()); // @todo: handle unwrap
-
-    println!("{:?}", binded_ast);
-}
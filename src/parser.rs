@@ -1,64 +1,282 @@
 use crate::errors::ParsingError;
-use crate::lexer::{Lexer, TokenType};
-use crate::types::module::Module;
+use crate::lexer::{Lexer, Span, Token, TokenType};
+use crate::types::{module::Module, statement::Statement};
 
-pub fn parse(lexer: &mut Lexer) -> Result<Module, ParsingError> {
-    lexer.next();
-    Module::parse(lexer)
+pub struct Parser<'a> {
+    lexer: Lexer<'a>,
+    errors: Vec<ParsingError>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(lexer: Lexer<'a>) -> Parser<'a> {
+        Parser {
+            lexer,
+            errors: vec![],
+        }
+    }
+
+    pub fn get(&self) -> Option<Token> {
+        self.lexer.get()
+    }
+
+    pub fn get_type(&self) -> Option<&TokenType> {
+        self.lexer.get_type()
+    }
+
+    pub fn get_token_start(&self) -> usize {
+        self.lexer.get_token_start()
+    }
+
+    pub fn get_span_from(&self, start: usize) -> Span {
+        self.lexer.get_span_from(start)
+    }
+
+    pub fn get_token_span(&self) -> Span {
+        self.lexer.get_token_span()
+    }
+
+    pub fn has_preceding_line_break(&self) -> bool {
+        self.lexer
+            .get_current()
+            .is_some_and(|token| token.preceded_by_line_break)
+    }
+
+    pub fn rescan_greater_than(&mut self) -> Token {
+        self.lexer.rescan_greater_than()
+    }
+
+    pub fn take_comments(&mut self) -> Vec<Token> {
+        self.lexer.take_comments()
+    }
+
+    pub fn get_current(&self) -> Option<&Token> {
+        self.lexer.get_current()
+    }
+
+    /// Runs `callback` and then rewinds, so the parser can decide what to parse by peeking at
+    /// the tokens that follow.
+    pub fn look_ahead<T>(&mut self, callback: impl FnOnce(&mut Parser<'a>) -> T) -> T {
+        let state = self.lexer.save_state();
+        let errors = self.errors.len();
+
+        let result = callback(self);
+
+        self.lexer.restore_state(state);
+        self.errors.truncate(errors);
+
+        result
+    }
+
+    /// Records a syntax error, so parsing can carry on past it.
+    pub fn report(&mut self, error: ParsingError) {
+        // The lexer already reported the character, complaining about the token is just noise
+        if let ParsingError::UnexpectedTokenError { found, .. }
+        | ParsingError::ExpressionExpectedError { found } = &error
+        {
+            if found.token_type == TokenType::Unknown {
+                return;
+            }
+        }
+
+        // Only the first error at a position is kept, the rest usually stem from the same mistake
+        if let Some(last) = self.errors.last() {
+            if last.span().start == error.span().start {
+                return;
+            }
+        }
+
+        self.errors.push(error);
+    }
+}
+
+impl Iterator for Parser<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.lexer.next();
+
+        for error in self.lexer.take_errors() {
+            self.errors.push(ParsingError::InvalidTokenError {
+                span: error.span(),
+                error,
+            });
+        }
+
+        token
+    }
+}
+
+/// Parses the whole file. Syntax errors do not stop the parser, so the returned module holds
+/// every statement that could be recovered, alongside all the errors that were found.
+pub fn parse(lexer: Lexer) -> (Module, Vec<ParsingError>) {
+    let mut parser = Parser::new(lexer);
+    parser.next();
+
+    let module = Module::parse(&mut parser);
+
+    (module, parser.errors)
 }
 
 pub fn parse_sequence<T>(
-    lexer: &mut Lexer,
-    parse_element: fn(&mut Lexer) -> Result<T, ParsingError>,
+    parser: &mut Parser,
+    parse_element: fn(&mut Parser) -> Result<T, ParsingError>,
     separator: TokenType,
     terminator: TokenType,
 ) -> Result<Vec<T>, ParsingError> {
     let mut seq = Vec::new();
-    while !try_consume_token(lexer, &terminator) {
-        match parse_element(lexer) {
+    while !try_consume_token(parser, &terminator) {
+        match parse_element(parser) {
             Ok(element) => seq.push(element),
             Err(err) => return Err(err),
         };
 
-        try_consume_token(lexer, &separator);
+        if !try_consume_token(parser, &separator) {
+            if try_consume_token(parser, &terminator) {
+                break;
+            }
+
+            return Err(create_unexpected_token_error(
+                parser,
+                vec![separator, terminator],
+            ));
+        }
     }
 
     Ok(seq)
 }
 
-pub fn try_consume_token(lexer: &mut Lexer, expected: &TokenType) -> bool {
-    let ok = match lexer.get_type() {
+/// Like `parse_sequence` for statements, but a statement that fails to parse is reported and
+/// skipped, instead of failing the whole list.
+pub fn parse_statements(parser: &mut Parser, terminator: TokenType) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    while !try_consume_token(parser, &terminator) {
+        // Empty statement
+        if try_consume_token(parser, &TokenType::Semicolon) {
+            continue;
+        }
+
+        let start = parser.get_token_start();
+
+        match Statement::parse(parser) {
+            Ok(statement) => statements.push(statement),
+            Err(err) => {
+                parser.report(err);
+                skip_statement(parser, &terminator, start);
+            }
+        };
+    }
+
+    statements
+}
+
+/// Skips tokens up to the next point where a statement can start: after a `;`, before a
+/// statement keyword or before the end of the enclosing list.
+fn skip_statement(parser: &mut Parser, terminator: &TokenType, start: usize) {
+    // Always make progress, otherwise a statement failing on its first token loops forever
+    if parser.get_token_start() == start && parser.get_type() != Some(&TokenType::EOF) {
+        parser.next();
+    }
+
+    loop {
+        match parser.get_type() {
+            Some(TokenType::EOF) => return,
+            Some(TokenType::Semicolon) => {
+                parser.next();
+                return;
+            }
+            Some(token_type) if token_type == terminator => return,
+            Some(TokenType::Var | TokenType::Type | TokenType::Return | TokenType::Function) => {
+                return
+            }
+            _ => {
+                parser.next();
+            }
+        }
+    }
+}
+
+/// Whether a statement may end before the current token, either with a `;` or with one
+/// inserted automatically: before a `}`, at the end of the file or after a line break.
+pub fn can_parse_semicolon(parser: &Parser) -> bool {
+    matches!(
+        parser.get_type(),
+        Some(TokenType::Semicolon | TokenType::CloseBrace | TokenType::EOF)
+    ) || parser.has_preceding_line_break()
+}
+
+/// Ends a statement. A missing `;` is reported, but parsing carries on with the next statement
+/// from the current token, so `a = 1 b = 2` still yields both statements.
+pub fn parse_semicolon(parser: &mut Parser) {
+    if can_parse_semicolon(parser) {
+        try_consume_token(parser, &TokenType::Semicolon);
+    } else {
+        let error = create_unexpected_token_error(parser, vec![TokenType::Semicolon]);
+        parser.report(error);
+    }
+}
+
+pub fn try_consume_token(parser: &mut Parser, expected: &TokenType) -> bool {
+    let ok = match parser.get_type() {
         Some(token_type) => token_type == expected,
         _ => false,
     };
 
     if ok {
-        lexer.next();
+        parser.next();
     }
 
     ok
 }
 
 pub fn try_parse_prefixed<T>(
-    lexer: &mut Lexer,
-    parse_element: fn(&mut Lexer) -> Result<T, ParsingError>,
+    parser: &mut Parser,
+    parse_element: fn(&mut Parser) -> Result<T, ParsingError>,
     prefix: TokenType,
-) -> Option<T> {
-    if try_consume_token(lexer, &prefix) {
-        match parse_element(lexer) {
-            Ok(element) => Some(element),
-            Err(_) => None,
-        }
+) -> Result<Option<T>, ParsingError> {
+    if try_consume_token(parser, &prefix) {
+        parse_element(parser).map(Some)
     } else {
-        None
+        Ok(None)
     }
 }
 
-pub fn parse_expected(lexer: &mut Lexer, expected_type: TokenType) -> Result<(), ParsingError> {
-    match lexer.get_type() {
-        Some(token_type) if token_type == &expected_type => Ok({
-            lexer.next();
-        }),
-        _ => Err(ParsingError::UnexpectedEndOfFileError),
+pub fn parse_expected(parser: &mut Parser, expected_type: TokenType) -> Result<(), ParsingError> {
+    match parser.get_type() {
+        Some(token_type) if token_type == &expected_type => {
+            parser.next();
+            Ok(())
+        }
+        _ => Err(create_unexpected_token_error(parser, vec![expected_type])),
+    }
+}
+
+/// Describes why the current token does not fit, when `expected` is what would have.
+pub fn create_unexpected_token_error(parser: &Parser, expected: Vec<TokenType>) -> ParsingError {
+    match parser.get_current() {
+        Some(token) if token.token_type == TokenType::EOF => {
+            ParsingError::UnexpectedEndOfFileError {
+                expected,
+                span: token.span(),
+            }
+        }
+        Some(token) => ParsingError::UnexpectedTokenError {
+            expected,
+            found: Box::new(token.clone()),
+        },
+        None => ParsingError::UnexpectedEndOfFileError {
+            expected,
+            span: parser.get_token_span(),
+        },
+    }
+}
+
+pub fn create_expression_expected_error(parser: &Parser) -> ParsingError {
+    match parser.get_current() {
+        Some(token) if token.token_type != TokenType::EOF => {
+            ParsingError::ExpressionExpectedError {
+                found: Box::new(token.clone()),
+            }
+        }
+        _ => create_unexpected_token_error(parser, vec![]),
     }
 }
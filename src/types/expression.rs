@@ -1,39 +1,97 @@
 use crate::binder::{
-    create_child, create_children, create_empty_parent, create_optional_child, AstNode, Child,
-    Children, Meaning, OptionalChild, Parent, Table,
+    create_child, create_children, create_empty_parent, create_optional_child, declare_symbol,
+    get_parent, AstNode, Child, Children, Meaning, OptionalChild, Parent, Table,
 };
 use crate::errors::{BindingError, ParsingError};
-use crate::lexer::{Lexer, TokenType};
-use crate::parser::{parse_expected, parse_sequence, try_consume_token, try_parse_prefixed};
+use crate::lexer::{Span, TokenType};
+use crate::parser::{
+    create_expression_expected_error, parse_expected, parse_sequence, parse_statements,
+    try_consume_token, try_parse_prefixed, Parser,
+};
 use crate::types::{
     identifier::Identifier, parameter::Parameter, property_assignment::PropertyAssignment,
     statement::Statement, type_node::TypeNode, type_parameter::TypeParameter,
 };
 use core::panic;
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 #[derive(Debug)]
 pub enum Expression {
-    Identifier(Child<Identifier>),
+    Identifier {
+        parent: Parent,
+        span: Span,
+        name: Child<Identifier>,
+    },
     NumericLiteral {
-        value: i64,
+        span: Span,
+        value: f64,
+    },
+    BigIntLiteral {
+        span: Span,
+        // Decimal digits, bigints have no size limit
+        value: String,
     },
     StringLiteral {
+        span: Span,
+        // Source text including the quotes and escapes, `value` is the string it stands for
+        raw: String,
         value: String,
     },
+    BooleanLiteral {
+        span: Span,
+        value: bool,
+    },
+    NullLiteral {
+        span: Span,
+    },
+    This {
+        span: Span,
+    },
     Assignment {
         parent: Parent,
-        name: Child<Identifier>,
+        span: Span,
+        target: Child<Expression>,
+        // `=` or a compound assignment like `+=`
+        operator: TokenType,
         value: Child<Expression>,
     },
+    Binary {
+        parent: Parent,
+        span: Span,
+        left: Child<Expression>,
+        operator: TokenType,
+        right: Child<Expression>,
+    },
+    Unary {
+        parent: Parent,
+        span: Span,
+        operator: TokenType,
+        operand: Child<Expression>,
+    },
+    // `++` and `--`, which are the only operators that can also come after their operand
+    Update {
+        parent: Parent,
+        span: Span,
+        operator: TokenType,
+        prefix: bool,
+        operand: Child<Expression>,
+    },
+    Parenthesized {
+        parent: Parent,
+        span: Span,
+        expression: Child<Expression>,
+    },
     Object {
         parent: Parent,
+        span: Span,
         properties: Children<PropertyAssignment>,
         members: RefCell<Table>,
     },
     Function {
         parent: Parent,
+        span: Span,
         name: OptionalChild<Identifier>,
         type_parameters: Children<TypeParameter>,
         parameters: Children<Parameter>,
@@ -41,12 +99,36 @@ pub enum Expression {
         body: Children<Statement>,
         locals: RefCell<Table>,
     },
+    ArrowFunction {
+        parent: Parent,
+        span: Span,
+        is_async: bool,
+        type_parameters: Children<TypeParameter>,
+        parameters: Children<Parameter>,
+        typename: OptionalChild<TypeNode>,
+        // A concise body like `x => x` is stored as `x => { return x }`
+        body: Children<Statement>,
+        locals: RefCell<Table>,
+    },
     Call {
         parent: Parent,
+        span: Span,
         expression: Child<Expression>,
         type_arguments: Children<TypeNode>,
         arguments: Children<Expression>,
     },
+    PropertyAccess {
+        parent: Parent,
+        span: Span,
+        expression: Child<Expression>,
+        name: Child<Identifier>,
+    },
+    ElementAccess {
+        parent: Parent,
+        span: Span,
+        expression: Child<Expression>,
+        argument: Child<Expression>,
+    },
 }
 
 impl AstNode for Expression {
@@ -57,80 +139,184 @@ impl AstNode for Expression {
     fn get_name(&self) -> String {
         match self {
             Expression::Object { .. } => String::from("__object"),
+            Expression::Function { name, .. } => match name.borrow().as_ref() {
+                Some(name) => name.text.clone(),
+                None => String::from("__function"),
+            },
+            Expression::ArrowFunction { .. } => String::from("__function"),
             _ => panic!("Cannot get name of the expression"),
         }
     }
+
+    fn get_span(&self) -> Span {
+        match self {
+            Expression::Identifier { span, .. }
+            | Expression::NumericLiteral { span, .. }
+            | Expression::BigIntLiteral { span, .. }
+            | Expression::StringLiteral { span, .. }
+            | Expression::BooleanLiteral { span, .. }
+            | Expression::NullLiteral { span }
+            | Expression::This { span }
+            | Expression::Assignment { span, .. }
+            | Expression::Binary { span, .. }
+            | Expression::Unary { span, .. }
+            | Expression::Update { span, .. }
+            | Expression::Parenthesized { span, .. }
+            | Expression::Object { span, .. }
+            | Expression::Function { span, .. }
+            | Expression::ArrowFunction { span, .. }
+            | Expression::Call { span, .. }
+            | Expression::PropertyAccess { span, .. }
+            | Expression::ElementAccess { span, .. } => *span,
+        }
+    }
+
+    fn get_name_span(&self) -> Span {
+        match self {
+            Expression::Function { name, .. } => match name.borrow().as_ref() {
+                Some(name) => name.span,
+                None => self.get_span(),
+            },
+            _ => self.get_span(),
+        }
+    }
+
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>> {
+        match self {
+            Expression::NumericLiteral { .. }
+            | Expression::BigIntLiteral { .. }
+            | Expression::StringLiteral { .. }
+            | Expression::BooleanLiteral { .. }
+            | Expression::NullLiteral { .. }
+            | Expression::This { .. } => None,
+            Expression::Identifier { parent, .. }
+            | Expression::Assignment { parent, .. }
+            | Expression::Binary { parent, .. }
+            | Expression::Unary { parent, .. }
+            | Expression::Update { parent, .. }
+            | Expression::Parenthesized { parent, .. }
+            | Expression::Object { parent, .. }
+            | Expression::Function { parent, .. }
+            | Expression::ArrowFunction { parent, .. }
+            | Expression::Call { parent, .. }
+            | Expression::PropertyAccess { parent, .. }
+            | Expression::ElementAccess { parent, .. } => get_parent(parent),
+        }
+    }
+
+    fn get_locals(&self) -> Option<&RefCell<Table>> {
+        match self {
+            Expression::Function { locals, .. } | Expression::ArrowFunction { locals, .. } => {
+                Some(locals)
+            }
+            _ => None,
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl Expression {
-    pub fn parse(lexer: &mut Lexer) -> Result<Expression, ParsingError> {
-        let expression = Expression::parse_below_call(lexer)?;
+    pub fn parse(parser: &mut Parser) -> Result<Expression, ParsingError> {
+        if parser.look_ahead(is_arrow_function) {
+            return Expression::parse_arrow_function(parser);
+        }
 
-        let type_arguments = if try_consume_token(lexer, &TokenType::LessThan) {
-            parse_sequence(
-                lexer,
-                TypeNode::parse,
-                TokenType::Comma,
-                TokenType::GreaterThan,
-            )?
-        } else {
-            vec![]
+        let start = parser.get_token_start();
+        let left = Expression::parse_binary(parser, 0)?;
+
+        let operator = match parser.get_type() {
+            Some(token_type) if is_assignment_operator(token_type) => token_type.clone(),
+            _ => return Ok(left),
         };
 
-        if try_consume_token(lexer, &TokenType::OpenParen) {
-            let arguments = parse_sequence(
-                lexer,
-                Expression::parse,
-                TokenType::Comma,
-                TokenType::CloseParen,
-            )?;
+        parser.next();
+        // Assignment is right-associative, `a = b = c` assigns `c` to both
+        let value = Expression::parse(parser)?;
 
-            Ok(Expression::Call {
-                parent: create_empty_parent(),
-                expression: create_child(expression),
-                type_arguments: create_children(type_arguments),
-                arguments: create_children(arguments),
-            })
-        } else {
-            Ok(expression)
-        }
+        // Any left-hand side parses, the checker rejects the ones that can't be assigned to
+        Ok(Expression::Assignment {
+            parent: create_empty_parent(),
+            span: parser.get_span_from(start),
+            target: create_child(left),
+            operator,
+            value: create_child(value),
+        })
     }
 
-    pub fn bind(self: &Rc<Self>, parent: &Rc<dyn AstNode>) -> Result<(), BindingError> {
+    pub fn bind(self: &Rc<Self>, parent: &Rc<dyn AstNode>, errors: &mut Vec<BindingError>) {
         let parent_weak = Rc::downgrade(parent);
         let self_rc = Rc::clone(self) as Rc<dyn AstNode>;
+        // The statement declares a function in statement position in the enclosing scope
+        let is_declaration = matches!(
+            parent.as_any().downcast_ref::<Statement>(),
+            Some(Statement::ExpressionStatement { .. })
+        );
 
         match &**self {
-            Expression::Identifier(name) => {
-                name.borrow().bind(&self_rc)?;
+            Expression::Identifier { name, parent, .. } => {
+                *parent.borrow_mut() = Some(parent_weak);
 
-                Ok(())
+                name.borrow().bind(&self_rc);
             }
-            Expression::NumericLiteral { .. } | Expression::StringLiteral { .. } => Ok(()),
+            Expression::NumericLiteral { .. }
+            | Expression::BigIntLiteral { .. }
+            | Expression::StringLiteral { .. }
+            | Expression::BooleanLiteral { .. }
+            | Expression::NullLiteral { .. }
+            | Expression::This { .. } => {}
             Expression::Assignment {
-                name,
+                target,
                 value,
                 parent,
+                ..
+            } => {
+                *parent.borrow_mut() = Some(parent_weak);
+
+                target.borrow().bind(&self_rc, errors);
+                value.borrow().bind(&self_rc, errors);
+            }
+            Expression::Binary {
+                left,
+                right,
+                parent,
+                ..
             } => {
                 *parent.borrow_mut() = Some(parent_weak);
 
-                name.borrow().bind(&self_rc)?;
-                value.borrow().bind(&self_rc)?;
+                left.borrow().bind(&self_rc, errors);
+                right.borrow().bind(&self_rc, errors);
+            }
+            Expression::Unary {
+                operand, parent, ..
+            }
+            | Expression::Update {
+                operand, parent, ..
+            } => {
+                *parent.borrow_mut() = Some(parent_weak);
 
-                Ok(())
+                operand.borrow().bind(&self_rc, errors);
+            }
+            Expression::Parenthesized {
+                expression, parent, ..
+            } => {
+                *parent.borrow_mut() = Some(parent_weak);
+
+                expression.borrow().bind(&self_rc, errors);
             }
             Expression::Object {
                 properties,
                 parent,
                 members,
+                ..
             } => {
                 *parent.borrow_mut() = Some(parent_weak);
 
                 for property in properties.borrow().iter() {
-                    property.bind(&self_rc, &mut members.borrow_mut())?;
+                    property.bind(&self_rc, &mut members.borrow_mut(), errors);
                 }
-
-                Ok(())
             }
             Expression::Function {
                 name,
@@ -140,79 +326,331 @@ impl Expression {
                 body,
                 parent,
                 locals,
+                ..
             } => {
                 *parent.borrow_mut() = Some(parent_weak);
 
                 if let Some(name_rc) = name.borrow().as_ref() {
-                    name_rc.bind(&self_rc)?;
+                    name_rc.bind(&self_rc);
                 }
 
                 if let Some(typename_rc) = typename.borrow().as_ref() {
-                    typename_rc.bind(&self_rc)?;
+                    typename_rc.bind(&self_rc, errors);
                 }
 
                 for type_parameter in type_parameters.borrow().iter() {
-                    type_parameter.bind(&self_rc, &mut locals.borrow_mut())?;
+                    type_parameter.bind(&self_rc, &mut locals.borrow_mut(), errors);
                 }
 
                 for parameter in parameters.borrow().iter() {
-                    parameter.bind(&self_rc, &mut locals.borrow_mut())?;
+                    parameter.bind(&self_rc, &mut locals.borrow_mut(), errors);
                 }
 
                 for statement in body.borrow().iter() {
-                    statement.bind(&self_rc, &mut locals.borrow_mut())?;
+                    statement.bind(&self_rc, &mut locals.borrow_mut(), errors);
+                }
+
+                // A named function expression can refer to itself, e.g.
+                // `var f = function g() { g() }`, unless a parameter or local shadows the name
+                if let Some(name_rc) = name.borrow().as_ref() {
+                    if !is_declaration && !locals.borrow().contains_key(&name_rc.text) {
+                        declare_symbol(&mut locals.borrow_mut(), &self_rc, errors);
+                    }
+                }
+            }
+            Expression::ArrowFunction {
+                type_parameters,
+                parameters,
+                typename,
+                body,
+                parent,
+                locals,
+                ..
+            } => {
+                *parent.borrow_mut() = Some(parent_weak);
+
+                if let Some(typename_rc) = typename.borrow().as_ref() {
+                    typename_rc.bind(&self_rc, errors);
+                }
+
+                for type_parameter in type_parameters.borrow().iter() {
+                    type_parameter.bind(&self_rc, &mut locals.borrow_mut(), errors);
                 }
 
-                Ok(())
+                for parameter in parameters.borrow().iter() {
+                    parameter.bind(&self_rc, &mut locals.borrow_mut(), errors);
+                }
+
+                for statement in body.borrow().iter() {
+                    statement.bind(&self_rc, &mut locals.borrow_mut(), errors);
+                }
             }
             Expression::Call {
                 expression,
                 type_arguments,
                 arguments,
                 parent,
+                ..
             } => {
                 *parent.borrow_mut() = Some(parent_weak);
 
-                expression.borrow().bind(&self_rc)?;
+                expression.borrow().bind(&self_rc, errors);
 
                 for type_argument in type_arguments.borrow().iter() {
-                    type_argument.bind(&self_rc)?;
+                    type_argument.bind(&self_rc, errors);
                 }
 
                 for argument in arguments.borrow().iter() {
-                    argument.bind(&self_rc)?;
+                    argument.bind(&self_rc, errors);
                 }
+            }
+            Expression::PropertyAccess {
+                expression,
+                name,
+                parent,
+                ..
+            } => {
+                *parent.borrow_mut() = Some(parent_weak);
+
+                expression.borrow().bind(&self_rc, errors);
+                name.borrow().bind(&self_rc);
+            }
+            Expression::ElementAccess {
+                expression,
+                argument,
+                parent,
+                ..
+            } => {
+                *parent.borrow_mut() = Some(parent_weak);
 
-                Ok(())
+                expression.borrow().bind(&self_rc, errors);
+                argument.borrow().bind(&self_rc, errors);
             }
         }
     }
 
-    fn parse_below_call(lexer: &mut Lexer) -> Result<Expression, ParsingError> {
-        if try_consume_token(lexer, &TokenType::OpenBrace) {
-            let properties = parse_sequence(
-                lexer,
-                PropertyAssignment::parse,
-                TokenType::Comma,
-                TokenType::CloseBrace,
-            )?;
+    fn parse_arrow_function(parser: &mut Parser) -> Result<Expression, ParsingError> {
+        let start = parser.get_token_start();
 
-            Ok(Expression::Object {
+        // `async => 1` is a parameter named `async`, not an async arrow
+        let is_async = parser.get_type() == Some(&TokenType::Async)
+            && parser.look_ahead(|parser| {
+                parser.next();
+                parser.get_type() != Some(&TokenType::Arrow)
+            });
+        if is_async {
+            parser.next();
+        }
+
+        let (type_parameters, parameters, typename) =
+            if parser.get_type().is_some_and(TokenType::is_identifier) {
+                (vec![], vec![Parameter::parse(parser)?], None)
+            } else {
+                let type_parameters = if try_consume_token(parser, &TokenType::LessThan) {
+                    parse_sequence(
+                        parser,
+                        TypeParameter::parse,
+                        TokenType::Comma,
+                        TokenType::GreaterThan,
+                    )?
+                } else {
+                    vec![]
+                };
+
+                parse_expected(parser, TokenType::OpenParen)?;
+
+                let parameters = parse_sequence(
+                    parser,
+                    Parameter::parse,
+                    TokenType::Comma,
+                    TokenType::CloseParen,
+                )?;
+
+                let typename = try_parse_prefixed(parser, TypeNode::parse, TokenType::Colon)?;
+
+                (type_parameters, parameters, typename)
+            };
+
+        parse_expected(parser, TokenType::Arrow)?;
+
+        let body = if try_consume_token(parser, &TokenType::OpenBrace) {
+            parse_statements(parser, TokenType::CloseBrace)
+        } else {
+            let expression = Expression::parse(parser)?;
+
+            vec![Statement::Return {
                 parent: create_empty_parent(),
-                properties: create_children(properties),
-                members: RefCell::new(Table::new()),
-            })
-        } else if try_consume_token(lexer, &TokenType::Function) {
-            let name = if Some(&TokenType::Identifier) == lexer.get_type() {
-                Some(Identifier::parse(lexer)?)
+                span: expression.get_span(),
+                expression: create_optional_child(Some(expression)),
+            }]
+        };
+
+        Ok(Expression::ArrowFunction {
+            parent: create_empty_parent(),
+            span: parser.get_span_from(start),
+            is_async,
+            type_parameters: create_children(type_parameters),
+            parameters: create_children(parameters),
+            typename: create_optional_child(typename),
+            body: create_children(body),
+            locals: RefCell::new(Table::new()),
+        })
+    }
+
+    /// Precedence climbing, binds every operator tighter than `precedence` into one expression.
+    fn parse_binary(parser: &mut Parser, precedence: u8) -> Result<Expression, ParsingError> {
+        let start = parser.get_token_start();
+        let mut left = Expression::parse_unary(parser)?;
+
+        loop {
+            // `>>` and `>=` are lexed as separate `>` tokens because of type arguments
+            parser.rescan_greater_than();
+
+            let operator = match parser.get_type() {
+                Some(token_type) => token_type.clone(),
+                None => break,
+            };
+            let Some(operator_precedence) = get_binary_precedence(&operator) else {
+                break;
+            };
+
+            // `**` is right-associative, `2 ** 3 ** 2` is `2 ** (3 ** 2)`
+            let binds_tighter = if operator == TokenType::AsteriskAsterisk {
+                operator_precedence >= precedence
             } else {
-                None
+                operator_precedence > precedence
             };
+            if !binds_tighter {
+                break;
+            }
+
+            // `-2 ** 2` is ambiguous, the unary operand has to be parenthesized
+            if let (TokenType::AsteriskAsterisk, Expression::Unary { operator, span, .. }) =
+                (&operator, &left)
+            {
+                parser.report(ParsingError::UnaryBeforeExponentiationError {
+                    operator: operator.clone(),
+                    span: *span,
+                });
+            }
+
+            parser.next();
+            let right = Expression::parse_binary(parser, operator_precedence)?;
+
+            left = Expression::Binary {
+                parent: create_empty_parent(),
+                span: parser.get_span_from(start),
+                left: create_child(left),
+                operator,
+                right: create_child(right),
+            };
+        }
+
+        Ok(left)
+    }
+
+    fn parse_unary(parser: &mut Parser) -> Result<Expression, ParsingError> {
+        let start = parser.get_token_start();
+
+        match parser.get_type() {
+            Some(
+                TokenType::Exclamation
+                | TokenType::Tilde
+                | TokenType::Plus
+                | TokenType::Minus
+                | TokenType::Typeof
+                | TokenType::Void
+                | TokenType::Delete,
+            ) => {
+                let operator = parser.get_type().unwrap().clone();
+                parser.next();
+                let operand = Expression::parse_unary(parser)?;
+
+                Ok(Expression::Unary {
+                    parent: create_empty_parent(),
+                    span: parser.get_span_from(start),
+                    operator,
+                    operand: create_child(operand),
+                })
+            }
+            Some(TokenType::PlusPlus | TokenType::MinusMinus) => {
+                let operator = parser.get_type().unwrap().clone();
+                parser.next();
+                let operand = Expression::parse_unary(parser)?;
+
+                Ok(Expression::Update {
+                    parent: create_empty_parent(),
+                    span: parser.get_span_from(start),
+                    operator,
+                    prefix: true,
+                    operand: create_child(operand),
+                })
+            }
+            _ => {
+                let operand = Expression::parse_call(parser)?;
+
+                // `a \n ++b` is two statements, postfix operators must be on the same line
+                match parser.get_type() {
+                    Some(TokenType::PlusPlus | TokenType::MinusMinus)
+                        if !parser.has_preceding_line_break() =>
+                    {
+                        let operator = parser.get_type().unwrap().clone();
+                        parser.next();
+
+                        Ok(Expression::Update {
+                            parent: create_empty_parent(),
+                            span: parser.get_span_from(start),
+                            operator,
+                            prefix: false,
+                            operand: create_child(operand),
+                        })
+                    }
+                    _ => Ok(operand),
+                }
+            }
+        }
+    }
+
+    /// Parses any chain of calls and member accesses, e.g. `f<T>()(x).y[0]()`.
+    fn parse_call(parser: &mut Parser) -> Result<Expression, ParsingError> {
+        let start = parser.get_token_start();
+        let mut expression = Expression::parse_below_call(parser)?;
+
+        loop {
+            if try_consume_token(parser, &TokenType::Dot) {
+                let name = Identifier::parse_name(parser)?;
+
+                expression = Expression::PropertyAccess {
+                    parent: create_empty_parent(),
+                    span: parser.get_span_from(start),
+                    expression: create_child(expression),
+                    name: create_child(name),
+                };
+                continue;
+            }
 
-            let type_parameters = if try_consume_token(lexer, &TokenType::LessThan) {
+            if try_consume_token(parser, &TokenType::OpenBracket) {
+                let argument = Expression::parse(parser)?;
+                parse_expected(parser, TokenType::CloseBracket)?;
+
+                expression = Expression::ElementAccess {
+                    parent: create_empty_parent(),
+                    span: parser.get_span_from(start),
+                    expression: create_child(expression),
+                    argument: create_child(argument),
+                };
+                continue;
+            }
+
+            // `f<T>(x)` is a call, but `a < b` is a comparison, so `<` only starts type
+            // arguments when they parse and are followed by the argument list
+            let type_arguments = if parser.get_type() == Some(&TokenType::LessThan)
+                && parser.look_ahead(is_type_argument_list)
+            {
+                parser.next();
                 parse_sequence(
-                    lexer,
-                    TypeParameter::parse,
+                    parser,
+                    TypeNode::parse,
                     TokenType::Comma,
                     TokenType::GreaterThan,
                 )?
@@ -220,73 +658,391 @@ impl Expression {
                 vec![]
             };
 
-            parse_expected(lexer, TokenType::OpenParen)?;
+            if !try_consume_token(parser, &TokenType::OpenParen) {
+                break;
+            }
 
-            let parameters = parse_sequence(
-                lexer,
-                Parameter::parse,
+            let arguments = parse_sequence(
+                parser,
+                Expression::parse,
                 TokenType::Comma,
                 TokenType::CloseParen,
             )?;
 
-            let typename = try_parse_prefixed(lexer, TypeNode::parse, TokenType::Colon);
+            expression = Expression::Call {
+                parent: create_empty_parent(),
+                span: parser.get_span_from(start),
+                expression: create_child(expression),
+                type_arguments: create_children(type_arguments),
+                arguments: create_children(arguments),
+            };
+        }
 
-            parse_expected(lexer, TokenType::OpenBrace)?;
+        Ok(expression)
+    }
 
-            let body = parse_sequence(
-                lexer,
-                Statement::parse,
-                TokenType::Semicolon,
-                TokenType::CloseBrace,
-            )?;
+    pub fn parse_function(parser: &mut Parser) -> Result<Expression, ParsingError> {
+        let start = parser.get_token_start();
+        parse_expected(parser, TokenType::Function)?;
 
-            Ok(Expression::Function {
-                parent: create_empty_parent(),
-                name: create_optional_child(name),
-                type_parameters: create_children(type_parameters),
-                parameters: create_children(parameters),
-                typename: create_optional_child(typename),
-                body: create_children(body),
-                locals: RefCell::new(Table::new()),
-            })
+        let name = if parser.get_type().is_some_and(TokenType::is_identifier) {
+            Some(Identifier::parse(parser)?)
         } else {
-            match lexer.get_type() {
-                Some(TokenType::Identifier) => Expression::parse_identifier_or_assignment(lexer),
-                Some(TokenType::StringLiteral) | Some(TokenType::NumericLiteral) => {
-                    Expression::parse_literal(lexer)
-                }
-                _ => Err(ParsingError::UnexpectedEndOfFileError),
-            }
-        }
+            None
+        };
+
+        let type_parameters = if try_consume_token(parser, &TokenType::LessThan) {
+            parse_sequence(
+                parser,
+                TypeParameter::parse,
+                TokenType::Comma,
+                TokenType::GreaterThan,
+            )?
+        } else {
+            vec![]
+        };
+
+        parse_expected(parser, TokenType::OpenParen)?;
+
+        let parameters = parse_sequence(
+            parser,
+            Parameter::parse,
+            TokenType::Comma,
+            TokenType::CloseParen,
+        )?;
+
+        let typename = try_parse_prefixed(parser, TypeNode::parse, TokenType::Colon)?;
+
+        parse_expected(parser, TokenType::OpenBrace)?;
+
+        let body = parse_statements(parser, TokenType::CloseBrace);
+
+        Ok(Expression::Function {
+            parent: create_empty_parent(),
+            span: parser.get_span_from(start),
+            name: create_optional_child(name),
+            type_parameters: create_children(type_parameters),
+            parameters: create_children(parameters),
+            typename: create_optional_child(typename),
+            body: create_children(body),
+            locals: RefCell::new(Table::new()),
+        })
     }
 
-    fn parse_identifier_or_assignment(lexer: &mut Lexer) -> Result<Expression, ParsingError> {
-        let name = Identifier::parse(lexer)?;
+    fn parse_below_call(parser: &mut Parser) -> Result<Expression, ParsingError> {
+        let start = parser.get_token_start();
+
+        if try_consume_token(parser, &TokenType::OpenParen) {
+            let expression = Expression::parse(parser)?;
+            parse_expected(parser, TokenType::CloseParen)?;
+
+            Ok(Expression::Parenthesized {
+                parent: create_empty_parent(),
+                span: parser.get_span_from(start),
+                expression: create_child(expression),
+            })
+        } else if try_consume_token(parser, &TokenType::OpenBrace) {
+            let properties = parse_sequence(
+                parser,
+                PropertyAssignment::parse,
+                TokenType::Comma,
+                TokenType::CloseBrace,
+            )?;
 
-        if let Some(expression) = try_parse_prefixed(lexer, Expression::parse, TokenType::Equals) {
-            Ok(Expression::Assignment {
+            Ok(Expression::Object {
                 parent: create_empty_parent(),
-                name: create_child(name),
-                value: create_child(expression),
+                span: parser.get_span_from(start),
+                properties: create_children(properties),
+                members: RefCell::new(Table::new()),
             })
+        } else if parser.get_type() == Some(&TokenType::Function) {
+            Expression::parse_function(parser)
         } else {
-            Ok(Expression::Identifier(create_child(name)))
+            match parser.get_type() {
+                Some(token_type) if token_type.is_identifier() => {
+                    let name = Identifier::parse(parser)?;
+
+                    Ok(Expression::Identifier {
+                        parent: create_empty_parent(),
+                        span: name.span,
+                        name: create_child(name),
+                    })
+                }
+                Some(
+                    TokenType::StringLiteral
+                    | TokenType::NumericLiteral
+                    | TokenType::BigIntLiteral
+                    | TokenType::True
+                    | TokenType::False
+                    | TokenType::Null,
+                ) => Expression::parse_literal(parser),
+                Some(TokenType::This) => {
+                    let span = parser.get_token_span();
+                    parser.next();
+
+                    Ok(Expression::This { span })
+                }
+                _ => Err(create_expression_expected_error(parser)),
+            }
         }
     }
 
-    fn parse_literal(lexer: &mut Lexer) -> Result<Expression, ParsingError> {
-        match lexer.get_type() {
+    fn parse_literal(parser: &mut Parser) -> Result<Expression, ParsingError> {
+        match parser.get_type() {
             Some(TokenType::NumericLiteral) => {
-                let value = lexer.get().unwrap().text.parse::<i64>().unwrap();
-                lexer.next();
-                Ok(Expression::NumericLiteral { value })
+                let token = parser.get().unwrap();
+                parser.next();
+                Ok(Expression::NumericLiteral {
+                    span: token.span(),
+                    value: token.value.parse().unwrap_or(f64::NAN),
+                })
+            }
+            Some(TokenType::BigIntLiteral) => {
+                let token = parser.get().unwrap();
+                parser.next();
+                Ok(Expression::BigIntLiteral {
+                    span: token.span(),
+                    value: token.value,
+                })
             }
             Some(TokenType::StringLiteral) => {
-                let value = lexer.get().unwrap().text.clone();
-                lexer.next();
-                Ok(Expression::StringLiteral { value })
+                let token = parser.get().unwrap();
+                parser.next();
+                Ok(Expression::StringLiteral {
+                    span: token.span(),
+                    raw: token.text,
+                    value: token.value,
+                })
+            }
+            Some(TokenType::True | TokenType::False) => {
+                let token = parser.get().unwrap();
+                parser.next();
+                Ok(Expression::BooleanLiteral {
+                    span: token.span(),
+                    value: token.token_type == TokenType::True,
+                })
             }
-            _ => Err(ParsingError::UnexpectedEndOfFileError),
+            Some(TokenType::Null) => {
+                let token = parser.get().unwrap();
+                parser.next();
+                Ok(Expression::NullLiteral { span: token.span() })
+            }
+            _ => Err(create_expression_expected_error(parser)),
+        }
+    }
+}
+
+/// Whether an arrow function starts here, which for `(` can only be told apart from a
+/// parenthesized expression by finding the `=>` after the parameters.
+fn is_arrow_function(parser: &mut Parser) -> bool {
+    if parser.get_type().is_some_and(TokenType::is_identifier) {
+        let is_async = parser.get_type() == Some(&TokenType::Async);
+        parser.next();
+
+        if is_arrow(parser) {
+            return true;
+        }
+        // `async` only makes an arrow async when the parameters follow on the same line
+        if !is_async || parser.has_preceding_line_break() {
+            return false;
+        }
+        if parser.get_type().is_some_and(TokenType::is_identifier) {
+            parser.next();
+            return is_arrow(parser);
+        }
+    }
+
+    if try_consume_token(parser, &TokenType::LessThan)
+        && parse_sequence(
+            parser,
+            TypeParameter::parse,
+            TokenType::Comma,
+            TokenType::GreaterThan,
+        )
+        .is_err()
+    {
+        return false;
+    }
+
+    try_consume_token(parser, &TokenType::OpenParen)
+        && parse_sequence(
+            parser,
+            Parameter::parse,
+            TokenType::Comma,
+            TokenType::CloseParen,
+        )
+        .is_ok()
+        && try_parse_prefixed(parser, TypeNode::parse, TokenType::Colon).is_ok()
+        && is_arrow(parser)
+}
+
+// No line break is allowed between the parameters and the `=>`
+fn is_arrow(parser: &Parser) -> bool {
+    parser.get_type() == Some(&TokenType::Arrow) && !parser.has_preceding_line_break()
+}
+
+fn is_type_argument_list(parser: &mut Parser) -> bool {
+    parser.next();
+
+    parse_sequence(
+        parser,
+        TypeNode::parse,
+        TokenType::Comma,
+        TokenType::GreaterThan,
+    )
+    .is_ok()
+        && parser.get_type() == Some(&TokenType::OpenParen)
+}
+
+fn is_assignment_operator(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Equals
+            | TokenType::PlusEquals
+            | TokenType::MinusEquals
+            | TokenType::AsteriskEquals
+            | TokenType::AsteriskAsteriskEquals
+            | TokenType::SlashEquals
+            | TokenType::PercentEquals
+            | TokenType::LessThanLessThanEquals
+            | TokenType::GreaterThanGreaterThanEquals
+            | TokenType::GreaterThanGreaterThanGreaterThanEquals
+            | TokenType::AmpersandEquals
+            | TokenType::BarEquals
+            | TokenType::CaretEquals
+            | TokenType::AmpersandAmpersandEquals
+            | TokenType::BarBarEquals
+            | TokenType::QuestionQuestionEquals
+    )
+}
+
+/// Binary operators from loosest to tightest, the same levels TypeScript uses. Assignment is
+/// looser than all of them and is handled by `Expression::parse`.
+fn get_binary_precedence(token_type: &TokenType) -> Option<u8> {
+    match token_type {
+        TokenType::QuestionQuestion => Some(1),
+        TokenType::BarBar => Some(2),
+        TokenType::AmpersandAmpersand => Some(3),
+        TokenType::Bar => Some(4),
+        TokenType::Caret => Some(5),
+        TokenType::Ampersand => Some(6),
+        TokenType::EqualsEquals
+        | TokenType::ExclamationEquals
+        | TokenType::EqualsEqualsEquals
+        | TokenType::ExclamationEqualsEquals => Some(7),
+        TokenType::LessThan
+        | TokenType::GreaterThan
+        | TokenType::LessThanEquals
+        | TokenType::GreaterThanEquals
+        | TokenType::Instanceof
+        | TokenType::In => Some(8),
+        TokenType::LessThanLessThan
+        | TokenType::GreaterThanGreaterThan
+        | TokenType::GreaterThanGreaterThanGreaterThan => Some(9),
+        TokenType::Plus | TokenType::Minus => Some(10),
+        TokenType::Asterisk | TokenType::Slash | TokenType::Percent => Some(11),
+        TokenType::AsteriskAsterisk => Some(12),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::parse;
+
+    /// Parses `input` as one expression and prints it with every binary operation in parens.
+    fn shape(input: &str) -> String {
+        let mut parser = Parser::new(Lexer::new(input));
+        parser.next();
+
+        print(&Expression::parse(&mut parser).unwrap())
+    }
+
+    fn print(expression: &Expression) -> String {
+        match expression {
+            Expression::Identifier { name, .. } => name.borrow().text.clone(),
+            Expression::NumericLiteral { value, .. } => value.to_string(),
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => format!(
+                "({} {} {})",
+                print(&left.borrow()),
+                operator.to_string().trim_matches('`'),
+                print(&right.borrow())
+            ),
+            Expression::Unary {
+                operator, operand, ..
+            } => format!(
+                "({}{})",
+                operator.to_string().trim_matches('`'),
+                print(&operand.borrow())
+            ),
+            Expression::Assignment {
+                target,
+                operator,
+                value,
+                ..
+            } => format!(
+                "({} {} {})",
+                print(&target.borrow()),
+                operator.to_string().trim_matches('`'),
+                print(&value.borrow())
+            ),
+            Expression::Parenthesized { expression, .. } => print(&expression.borrow()),
+            _ => unimplemented!("Cannot print {:?}", expression),
         }
     }
+
+    #[test]
+    fn exponentiation_is_right_associative() {
+        assert_eq!(shape("2 ** 3 ** 2"), "(2 ** (3 ** 2))");
+    }
+
+    #[test]
+    fn rejects_unary_before_exponentiation() {
+        let (_, errors) = parse(Lexer::new("-2 ** 2;"));
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            ParsingError::UnaryBeforeExponentiationError {
+                operator: TokenType::Minus,
+                ..
+            }
+        ));
+        assert_eq!(errors[0].span().start, 0);
+        assert!(parse(Lexer::new("(-2) ** 2;")).1.is_empty());
+    }
+
+    #[test]
+    fn subtraction_is_left_associative() {
+        assert_eq!(shape("a - b - c"), "((a - b) - c)");
+    }
+
+    #[test]
+    fn binds_by_precedence() {
+        assert_eq!(shape("a + b * c - d"), "((a + (b * c)) - d)");
+        assert_eq!(shape("a || b && c ?? d"), "((a || (b && c)) ?? d)");
+        assert_eq!(shape("a == b < c + d"), "(a == (b < (c + d)))");
+        assert_eq!(shape("(-a) ** 2"), "((-a) ** 2)");
+        assert_eq!(shape("(a - b) * c"), "((a - b) * c)");
+    }
+
+    #[test]
+    fn rescans_greater_than_operators() {
+        assert_eq!(shape("a >> b >= c"), "((a >> b) >= c)");
+        assert_eq!(shape("a >>> b > c"), "((a >>> b) > c)");
+        assert_eq!(shape("a > b >= c"), "((a > b) >= c)");
+    }
+
+    #[test]
+    fn assignment_is_right_associative() {
+        assert_eq!(shape("a = b += c + 1"), "(a = (b += (c + 1)))");
+        assert_eq!(shape("a >>= b"), "(a >>= b)");
+    }
 }
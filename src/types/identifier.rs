@@ -1,12 +1,15 @@
 use crate::{
-    binder::{create_empty_parent, AstNode, Meaning, Parent},
-    errors::{BindingError, ParsingError},
-    lexer::{Lexer, TokenType},
+    binder::{create_empty_parent, get_parent, AstNode, Meaning, Parent},
+    errors::ParsingError,
+    lexer::{Span, TokenType},
+    parser::{create_unexpected_token_error, Parser},
 };
+use std::any::Any;
 use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct Identifier {
+    pub span: Span,
     pub parent: Parent,
     pub text: String,
 }
@@ -19,26 +22,55 @@ impl AstNode for Identifier {
     fn get_name(&self) -> String {
         self.text.clone()
     }
+
+    fn get_span(&self) -> Span {
+        self.span
+    }
+
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>> {
+        get_parent(&self.parent)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl Identifier {
-    pub fn parse(lexer: &mut Lexer) -> Result<Identifier, ParsingError> {
-        match lexer.get() {
-            Some(token) if token.token_type == TokenType::Identifier => {
-                lexer.next();
+    pub fn parse(parser: &mut Parser) -> Result<Identifier, ParsingError> {
+        match parser.get() {
+            Some(token) if token.token_type.is_identifier() => {
+                parser.next();
                 Ok(Identifier {
+                    span: token.span(),
                     text: token.text.clone(),
                     parent: create_empty_parent(),
                 })
             }
-            _ => Err(ParsingError::UnexpectedEndOfFileError),
+            _ => Err(create_unexpected_token_error(
+                parser,
+                vec![TokenType::Identifier],
+            )),
         }
     }
 
-    pub fn bind(self: &Rc<Self>, parent: &Rc<dyn AstNode>) -> Result<(), BindingError> {
+    /// Parses the name after a `.`, where reserved words are fine too, e.g. `promise.catch`.
+    pub fn parse_name(parser: &mut Parser) -> Result<Identifier, ParsingError> {
+        match parser.get() {
+            Some(token) if token.token_type.is_reserved_word() => {
+                parser.next();
+                Ok(Identifier {
+                    span: token.span(),
+                    text: token.text.clone(),
+                    parent: create_empty_parent(),
+                })
+            }
+            _ => Identifier::parse(parser),
+        }
+    }
+
+    pub fn bind(self: &Rc<Self>, parent: &Rc<dyn AstNode>) {
         let parent_weak = Rc::downgrade(parent);
         *self.parent.borrow_mut() = Some(parent_weak);
-
-        Ok(())
     }
 }
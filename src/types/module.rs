@@ -1,15 +1,18 @@
 use crate::binder::{create_children, AstNode, Children, Meaning, Table};
-use crate::errors::{BindingError, ParsingError};
-use crate::lexer::{Lexer, TokenType};
-use crate::parser::parse_sequence;
+use crate::errors::BindingError;
+use crate::lexer::{Span, Token, TokenType};
+use crate::parser::{parse_statements, Parser};
 use crate::types::statement::Statement;
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct Module {
+    pub span: Span,
     pub statements: Children<Statement>,
     pub locals: RefCell<Table>,
+    pub comments: Vec<Token>,
 }
 
 impl AstNode for Module {
@@ -20,31 +23,41 @@ impl AstNode for Module {
     fn get_name(&self) -> String {
         String::from("__module")
     }
+
+    fn get_span(&self) -> Span {
+        self.span
+    }
+
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>> {
+        None
+    }
+
+    fn get_locals(&self) -> Option<&RefCell<Table>> {
+        Some(&self.locals)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl Module {
-    pub fn parse(lexer: &mut Lexer) -> Result<Module, ParsingError> {
-        let statements = parse_sequence(
-            lexer,
-            Statement::parse,
-            TokenType::Semicolon,
-            TokenType::EOF,
-        )?;
-
-        let module = Module {
+    pub fn parse(parser: &mut Parser) -> Module {
+        let start = parser.get_token_start();
+        let statements = parse_statements(parser, TokenType::EOF);
+
+        Module {
+            span: parser.get_span_from(start),
             statements: create_children(statements),
             locals: RefCell::new(Table::new()),
-        };
-
-        Ok(module)
+            comments: parser.take_comments(),
+        }
     }
 
-    pub fn bind(self: &Rc<Self>) -> Result<(), BindingError> {
+    pub fn bind(self: &Rc<Self>, errors: &mut Vec<BindingError>) {
         let self_rc: Rc<dyn AstNode> = self.clone();
         for statement_rc in self.statements.borrow().iter() {
-            statement_rc.bind(&self_rc, &mut self.locals.borrow_mut())?;
+            statement_rc.bind(&self_rc, &mut self.locals.borrow_mut(), errors);
         }
-
-        Ok(())
     }
 }
@@ -1,17 +1,20 @@
 use crate::binder::{
-    create_child, create_empty_parent, declare_symbol, AstNode, Child, Meaning, Parent, Table,
+    create_child, create_empty_parent, declare_symbol, get_parent, AstNode, Child, Meaning, Parent,
+    Table,
 };
 use crate::errors::{BindingError, ParsingError};
-use crate::lexer::{Lexer, TokenType};
-use crate::parser::parse_expected;
+use crate::lexer::{Span, TokenType};
+use crate::parser::{parse_expected, Parser};
 use crate::types::{expression::Expression, identifier::Identifier};
+use std::any::Any;
 use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct PropertyAssignment {
-    parent: Parent,
-    name: Child<Identifier>,
-    value: Child<Expression>,
+    pub span: Span,
+    pub parent: Parent,
+    pub name: Child<Identifier>,
+    pub value: Child<Expression>,
 }
 
 impl AstNode for PropertyAssignment {
@@ -22,16 +25,34 @@ impl AstNode for PropertyAssignment {
     fn get_name(&self) -> String {
         self.name.borrow().text.clone()
     }
+
+    fn get_span(&self) -> Span {
+        self.span
+    }
+
+    fn get_name_span(&self) -> Span {
+        self.name.borrow().span
+    }
+
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>> {
+        get_parent(&self.parent)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl PropertyAssignment {
-    pub fn parse(lexer: &mut Lexer) -> Result<PropertyAssignment, ParsingError> {
-        let name = Identifier::parse(lexer)?;
-        parse_expected(lexer, TokenType::Colon)?;
+    pub fn parse(parser: &mut Parser) -> Result<PropertyAssignment, ParsingError> {
+        let start = parser.get_token_start();
+        let name = Identifier::parse(parser)?;
+        parse_expected(parser, TokenType::Colon)?;
 
-        let value = Expression::parse(lexer)?;
+        let value = Expression::parse(parser)?;
 
         Ok(PropertyAssignment {
+            span: parser.get_span_from(start),
             name: create_child(name),
             value: create_child(value),
             parent: create_empty_parent(),
@@ -42,16 +63,15 @@ impl PropertyAssignment {
         self: &Rc<Self>,
         parent: &Rc<dyn AstNode>,
         members: &mut Table,
-    ) -> Result<(), BindingError> {
+        errors: &mut Vec<BindingError>,
+    ) {
         let parent_weak = Rc::downgrade(parent);
         let self_rc = Rc::clone(self) as Rc<dyn AstNode>;
         *self.parent.borrow_mut() = Some(parent_weak);
 
-        self.name.borrow().bind(&self_rc)?;
-        self.value.borrow().bind(&self_rc)?;
-
-        declare_symbol(members, &self_rc)?;
+        self.name.borrow().bind(&self_rc);
+        self.value.borrow().bind(&self_rc, errors);
 
-        Ok(())
+        declare_symbol(members, &self_rc, errors);
     }
 }
@@ -1,18 +1,20 @@
 use crate::binder::{
-    create_child, create_empty_parent, create_optional_child, declare_symbol, AstNode, Child,
-    Meaning, OptionalChild, Parent, Table,
+    create_child, create_empty_parent, create_optional_child, declare_symbol, get_parent, AstNode,
+    Child, Meaning, OptionalChild, Parent, Table,
 };
 use crate::errors::{BindingError, ParsingError};
-use crate::lexer::{Lexer, TokenType};
-use crate::parser::try_parse_prefixed;
+use crate::lexer::{Span, TokenType};
+use crate::parser::{try_parse_prefixed, Parser};
 use crate::types::{identifier::Identifier, type_node::TypeNode};
+use std::any::Any;
 use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct PropertyDeclaration {
-    parent: Parent,
-    name: Child<Identifier>,
-    typename: OptionalChild<TypeNode>,
+    pub span: Span,
+    pub parent: Parent,
+    pub name: Child<Identifier>,
+    pub typename: OptionalChild<TypeNode>,
 }
 
 impl AstNode for PropertyDeclaration {
@@ -23,14 +25,32 @@ impl AstNode for PropertyDeclaration {
     fn get_name(&self) -> String {
         self.name.borrow().text.clone()
     }
+
+    fn get_span(&self) -> Span {
+        self.span
+    }
+
+    fn get_name_span(&self) -> Span {
+        self.name.borrow().span
+    }
+
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>> {
+        get_parent(&self.parent)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl PropertyDeclaration {
-    pub fn parse(lexer: &mut Lexer) -> Result<PropertyDeclaration, ParsingError> {
-        let name = Identifier::parse(lexer)?;
-        let typename = try_parse_prefixed(lexer, TypeNode::parse, TokenType::Colon);
+    pub fn parse(parser: &mut Parser) -> Result<PropertyDeclaration, ParsingError> {
+        let start = parser.get_token_start();
+        let name = Identifier::parse(parser)?;
+        let typename = try_parse_prefixed(parser, TypeNode::parse, TokenType::Colon)?;
 
         Ok(PropertyDeclaration {
+            span: parser.get_span_from(start),
             name: create_child(name),
             typename: create_optional_child(typename),
             parent: create_empty_parent(),
@@ -41,17 +61,18 @@ impl PropertyDeclaration {
         self: &Rc<Self>,
         parent: &Rc<dyn AstNode>,
         members: &mut Table,
-    ) -> Result<(), BindingError> {
+        errors: &mut Vec<BindingError>,
+    ) {
         let parent_weak = Rc::downgrade(parent);
         let self_rc = Rc::clone(self) as Rc<dyn AstNode>;
         *self.parent.borrow_mut() = Some(parent_weak);
 
-        self.name.borrow().bind(&self_rc)?;
+        self.name.borrow().bind(&self_rc);
 
         if let Some(type_node_rc) = self.typename.borrow().as_ref() {
-            type_node_rc.bind(&self_rc)?;
+            type_node_rc.bind(&self_rc, errors);
         }
 
-        declare_symbol(members, &self_rc)
+        declare_symbol(members, &self_rc, errors);
     }
 }
@@ -1,34 +1,43 @@
 use crate::binder::{
-    create_child, create_empty_parent, create_optional_child, declare_symbol, Child, OptionalChild,
-    Parent,
+    create_child, create_empty_parent, create_optional_child, declare_symbol, get_parent, Child,
+    OptionalChild, Parent,
 };
 use crate::binder::{AstNode, Meaning, Table};
 use crate::errors::{BindingError, ParsingError};
-use crate::lexer::{Lexer, TokenType};
-use crate::parser::{parse_expected, try_consume_token, try_parse_prefixed};
+use crate::lexer::{Span, TokenType};
+use crate::parser::{
+    can_parse_semicolon, parse_expected, parse_semicolon, try_consume_token, try_parse_prefixed,
+    Parser,
+};
 use crate::types::{expression::Expression, identifier::Identifier, type_node::TypeNode};
+use std::any::Any;
 use std::rc::Rc;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum Statement {
     Var {
         parent: Parent,
+        span: Span,
         name: Child<Identifier>,
         typename: OptionalChild<TypeNode>,
         initializer: Child<Expression>,
     },
     TypeAlias {
         parent: Parent,
+        span: Span,
         name: Child<Identifier>,
         typename: Child<TypeNode>,
     },
     ExpressionStatement {
         parent: Parent,
+        span: Span,
         expression: Child<Expression>,
     },
     Return {
         parent: Parent,
-        expression: Child<Expression>,
+        span: Span,
+        expression: OptionalChild<Expression>,
     },
 }
 
@@ -54,26 +63,69 @@ impl AstNode for Statement {
             }
         }
     }
+
+    fn get_span(&self) -> Span {
+        match self {
+            Statement::Var { span, .. }
+            | Statement::TypeAlias { span, .. }
+            | Statement::ExpressionStatement { span, .. }
+            | Statement::Return { span, .. } => *span,
+        }
+    }
+
+    fn get_name_span(&self) -> Span {
+        match self {
+            Statement::Var { name, .. } | Statement::TypeAlias { name, .. } => name.borrow().span,
+            _ => self.get_span(),
+        }
+    }
+
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>> {
+        match self {
+            Statement::Var { parent, .. }
+            | Statement::TypeAlias { parent, .. }
+            | Statement::ExpressionStatement { parent, .. }
+            | Statement::Return { parent, .. } => get_parent(parent),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl Statement {
-    pub fn parse(lexer: &mut Lexer) -> Result<Statement, ParsingError> {
-        if try_consume_token(lexer, &TokenType::Var) {
-            Statement::parse_var(lexer)
-        } else if try_consume_token(lexer, &TokenType::Type) {
-            Statement::parse_type_alias(lexer)
-        } else if try_consume_token(lexer, &TokenType::Return) {
-            Statement::parse_return(lexer)
+    pub fn parse(parser: &mut Parser) -> Result<Statement, ParsingError> {
+        let start = parser.get_token_start();
+
+        if try_consume_token(parser, &TokenType::Var) {
+            Statement::parse_var(parser, start)
+        } else if parser.get_type() == Some(&TokenType::Type) && Statement::is_type_alias(parser) {
+            parser.next();
+            Statement::parse_type_alias(parser, start)
+        } else if try_consume_token(parser, &TokenType::Return) {
+            Statement::parse_return(parser, start)
         } else {
-            Statement::parse_expression_statement(lexer)
+            Statement::parse_expression_statement(parser, start)
         }
     }
 
+    /// `type` starts an alias only when a name follows on the same line, otherwise it is a plain
+    /// identifier, as in `type = 1`.
+    fn is_type_alias(parser: &mut Parser) -> bool {
+        parser.look_ahead(|parser| {
+            parser.next();
+            parser.get_type().is_some_and(TokenType::is_identifier)
+                && !parser.has_preceding_line_break()
+        })
+    }
+
     pub fn bind(
         self: &Rc<Self>,
         parent: &Rc<dyn AstNode>,
         locals: &mut Table,
-    ) -> Result<(), BindingError> {
+        errors: &mut Vec<BindingError>,
+    ) {
         let self_rc = Rc::clone(self) as Rc<dyn AstNode>;
         let parent_weak = Rc::downgrade(parent);
 
@@ -86,86 +138,123 @@ impl Statement {
                 ..
             } => {
                 *parent.borrow_mut() = Some(parent_weak);
-                name.borrow().bind(&self_rc)?;
-                initializer.borrow().bind(&self_rc)?;
+                name.borrow().bind(&self_rc);
+                initializer.borrow().bind(&self_rc, errors);
 
                 if let Some(type_node_rc) = typename.borrow().as_ref() {
-                    type_node_rc.bind(&self_rc)?;
+                    type_node_rc.bind(&self_rc, errors);
                 }
 
-                declare_symbol(locals, &self_rc)
+                declare_symbol(locals, &self_rc, errors);
             }
             Statement::TypeAlias {
                 parent,
                 name,
                 typename,
+                ..
             } => {
                 *parent.borrow_mut() = Some(parent_weak);
-                name.borrow().bind(&self_rc)?;
-                typename.borrow().bind(&self_rc)?;
+                name.borrow().bind(&self_rc);
+                typename.borrow().bind(&self_rc, errors);
 
-                declare_symbol(locals, &self_rc)
+                declare_symbol(locals, &self_rc, errors);
             }
-            Statement::ExpressionStatement { parent, expression } => {
+            Statement::ExpressionStatement {
+                parent, expression, ..
+            } => {
                 *parent.borrow_mut() = Some(parent_weak);
-                expression.borrow().bind(&self_rc)?;
+                expression.borrow().bind(&self_rc, errors);
 
-                Ok(())
+                // Named functions in statement position are declarations, e.g. `function f() {}`
+                let expression_rc = Rc::clone(&expression.borrow()) as Rc<dyn AstNode>;
+                if let Expression::Function { name, .. } = &**expression.borrow() {
+                    if name.borrow().is_some() {
+                        declare_symbol(locals, &expression_rc, errors);
+                    }
+                }
             }
-            Statement::Return { parent, expression } => {
+            Statement::Return {
+                parent, expression, ..
+            } => {
                 *parent.borrow_mut() = Some(parent_weak);
-                expression.borrow().bind(&self_rc)?;
 
-                Ok(())
+                if let Some(expression) = expression.borrow().as_ref() {
+                    expression.bind(&self_rc, errors);
+                }
             }
         }
     }
 
-    fn parse_var(lexer: &mut Lexer) -> Result<Statement, ParsingError> {
-        let name = Identifier::parse(lexer)?;
-        let typename = try_parse_prefixed(lexer, TypeNode::parse, TokenType::Colon);
+    fn parse_var(parser: &mut Parser, start: usize) -> Result<Statement, ParsingError> {
+        let name = Identifier::parse(parser)?;
+        let typename = try_parse_prefixed(parser, TypeNode::parse, TokenType::Colon)?;
 
-        parse_expected(lexer, TokenType::Equals)?;
+        parse_expected(parser, TokenType::Equals)?;
 
-        let initializer = Expression::parse(lexer)?;
+        let initializer = Expression::parse(parser)?;
+        parse_semicolon(parser);
 
         Ok(Statement::Var {
             name: create_child(name),
             typename: create_optional_child(typename),
             initializer: create_child(initializer),
             parent: create_empty_parent(),
+            span: parser.get_span_from(start),
         })
     }
 
-    fn parse_type_alias(lexer: &mut Lexer) -> Result<Statement, ParsingError> {
-        let name = Identifier::parse(lexer)?;
+    fn parse_type_alias(parser: &mut Parser, start: usize) -> Result<Statement, ParsingError> {
+        let name = Identifier::parse(parser)?;
 
-        parse_expected(lexer, TokenType::Equals)?;
+        parse_expected(parser, TokenType::Equals)?;
 
-        let typename = TypeNode::parse(lexer)?;
+        let typename = TypeNode::parse(parser)?;
+        parse_semicolon(parser);
 
         Ok(Statement::TypeAlias {
             name: create_child(name),
             typename: create_child(typename),
             parent: create_empty_parent(),
+            span: parser.get_span_from(start),
         })
     }
 
-    fn parse_return(lexer: &mut Lexer) -> Result<Statement, ParsingError> {
-        let expression = Expression::parse(lexer)?;
+    fn parse_return(parser: &mut Parser, start: usize) -> Result<Statement, ParsingError> {
+        // `return` is a restricted production, a line break right after it ends the statement,
+        // so `return\n1` returns nothing
+        let expression = if can_parse_semicolon(parser) {
+            None
+        } else {
+            Some(Expression::parse(parser)?)
+        };
+        parse_semicolon(parser);
 
         Ok(Statement::Return {
-            expression: create_child(expression),
+            expression: create_optional_child(expression),
             parent: create_empty_parent(),
+            span: parser.get_span_from(start),
         })
     }
 
-    fn parse_expression_statement(lexer: &mut Lexer) -> Result<Statement, ParsingError> {
-        let expression = Expression::parse(lexer)?;
+    fn parse_expression_statement(
+        parser: &mut Parser,
+        start: usize,
+    ) -> Result<Statement, ParsingError> {
+        // A function declaration ends at its `}`, so neither an operator nor a call on the next
+        // line continues it, and it needs no `;`
+        let expression = if parser.get_type() == Some(&TokenType::Function) {
+            Expression::parse_function(parser)?
+        } else {
+            let expression = Expression::parse(parser)?;
+            parse_semicolon(parser);
+
+            expression
+        };
 
         Ok(Statement::ExpressionStatement {
             expression: create_child(expression),
             parent: create_empty_parent(),
+            span: parser.get_span_from(start),
         })
     }
 }
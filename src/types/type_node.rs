@@ -1,14 +1,15 @@
 use crate::binder::{
-    create_child, create_children, create_empty_parent, AstNode, Child, Children, Meaning, Parent,
-    Table,
+    create_child, create_children, create_empty_parent, get_parent, AstNode, Child, Children,
+    Meaning, Parent, Table,
 };
 use crate::errors::{BindingError, ParsingError};
-use crate::lexer::{Lexer, TokenType};
-use crate::parser::{parse_expected, parse_sequence, try_consume_token};
+use crate::lexer::{Span, TokenType};
+use crate::parser::{parse_expected, parse_sequence, try_consume_token, Parser};
 use crate::types::{
     identifier::Identifier, parameter::Parameter, property_declaration::PropertyDeclaration,
     type_parameter::TypeParameter,
 };
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -16,12 +17,18 @@ use std::rc::Rc;
 pub enum TypeNode {
     ObjectLiteralType {
         parent: Parent,
+        span: Span,
         properties: Children<PropertyDeclaration>,
         members: RefCell<Table>,
     },
-    Identifier(Child<Identifier>),
+    Identifier {
+        parent: Parent,
+        span: Span,
+        name: Child<Identifier>,
+    },
     SignatureDeclaration {
         parent: Parent,
+        span: Span,
         type_parameters: Children<TypeParameter>,
         parameters: Children<Parameter>,
         typename: Child<TypeNode>,
@@ -37,17 +44,46 @@ impl AstNode for TypeNode {
     fn get_name(&self) -> String {
         match self {
             TypeNode::ObjectLiteralType { .. } => String::from("__object"),
-            TypeNode::Identifier(identifier) => identifier.borrow().text.clone(),
+            TypeNode::Identifier { name, .. } => name.borrow().text.clone(),
             TypeNode::SignatureDeclaration { .. } => String::from("__signature"),
         }
     }
+
+    fn get_span(&self) -> Span {
+        match self {
+            TypeNode::ObjectLiteralType { span, .. }
+            | TypeNode::Identifier { span, .. }
+            | TypeNode::SignatureDeclaration { span, .. } => *span,
+        }
+    }
+
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>> {
+        match self {
+            TypeNode::ObjectLiteralType { parent, .. }
+            | TypeNode::Identifier { parent, .. }
+            | TypeNode::SignatureDeclaration { parent, .. } => get_parent(parent),
+        }
+    }
+
+    fn get_locals(&self) -> Option<&RefCell<Table>> {
+        match self {
+            TypeNode::SignatureDeclaration { locals, .. } => Some(locals),
+            _ => None,
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl TypeNode {
-    pub fn parse(lexer: &mut Lexer) -> Result<TypeNode, ParsingError> {
-        if try_consume_token(lexer, &TokenType::OpenBrace) {
+    pub fn parse(parser: &mut Parser) -> Result<TypeNode, ParsingError> {
+        let start = parser.get_token_start();
+
+        if try_consume_token(parser, &TokenType::OpenBrace) {
             let properties = parse_sequence(
-                lexer,
+                parser,
                 PropertyDeclaration::parse,
                 TokenType::Comma,
                 TokenType::CloseBrace,
@@ -55,64 +91,82 @@ impl TypeNode {
 
             Ok(TypeNode::ObjectLiteralType {
                 parent: create_empty_parent(),
+                span: parser.get_span_from(start),
                 properties: create_children(properties),
                 members: RefCell::new(Table::new()),
             })
-        } else if try_consume_token(lexer, &TokenType::LessThan) {
+        } else if try_consume_token(parser, &TokenType::LessThan) {
             let type_parameters = parse_sequence(
-                lexer,
+                parser,
                 TypeParameter::parse,
                 TokenType::Comma,
                 TokenType::GreaterThan,
             )?;
 
-            parse_expected(lexer, TokenType::OpenParen)?;
+            parse_expected(parser, TokenType::OpenParen)?;
 
             let parameters = parse_sequence(
-                lexer,
+                parser,
                 Parameter::parse,
                 TokenType::Comma,
                 TokenType::CloseParen,
             )?;
 
-            parse_expected(lexer, TokenType::Arrow)?;
+            parse_expected(parser, TokenType::Arrow)?;
 
-            let typename = TypeNode::parse(lexer)?;
+            let typename = TypeNode::parse(parser)?;
 
             Ok(TypeNode::SignatureDeclaration {
                 parent: create_empty_parent(),
+                span: parser.get_span_from(start),
                 type_parameters: create_children(type_parameters),
                 parameters: create_children(parameters),
                 typename: create_child(typename),
                 locals: RefCell::new(Table::new()),
             })
-        } else if try_consume_token(lexer, &TokenType::OpenParen) {
+        } else if try_consume_token(parser, &TokenType::OpenParen) {
             let parameters = parse_sequence(
-                lexer,
+                parser,
                 Parameter::parse,
                 TokenType::Comma,
                 TokenType::CloseParen,
             )?;
 
-            parse_expected(lexer, TokenType::Arrow)?;
+            parse_expected(parser, TokenType::Arrow)?;
 
-            let typename = TypeNode::parse(lexer)?;
+            let typename = TypeNode::parse(parser)?;
 
             Ok(TypeNode::SignatureDeclaration {
                 parent: create_empty_parent(),
+                span: parser.get_span_from(start),
                 type_parameters: create_children(vec![]),
                 parameters: create_children(parameters),
                 typename: create_child(typename),
                 locals: RefCell::new(Table::new()),
             })
         } else {
-            Ok(TypeNode::Identifier(create_child(Identifier::parse(
-                lexer,
-            )?)))
+            let name = match parser.get() {
+                // Reserved words, but in a type they name built-in types just like `number`
+                Some(token) if matches!(token.token_type, TokenType::Void | TokenType::Null) => {
+                    parser.next();
+                    Identifier {
+                        span: token.span(),
+                        parent: create_empty_parent(),
+                        text: token.text,
+                    }
+                }
+                _ => Identifier::parse(parser)?,
+            };
+
+            Ok(TypeNode::Identifier {
+                parent: create_empty_parent(),
+                span: name.span,
+                name: create_child(name),
+            })
         }
     }
 
-    pub fn bind(self: &Rc<Self>, parent: &Rc<dyn AstNode>) -> Result<(), BindingError> {
+    pub fn bind(self: &Rc<Self>, parent: &Rc<dyn AstNode>, errors: &mut Vec<BindingError>) {
         let parent_weak = Rc::downgrade(parent);
         let self_rc = Rc::clone(self) as Rc<dyn AstNode>;
 
@@ -121,19 +175,18 @@ impl TypeNode {
                 parent,
                 properties,
                 members,
+                ..
             } => {
                 *parent.borrow_mut() = Some(parent_weak);
 
                 for property in properties.borrow().iter() {
-                    property.bind(&self_rc, &mut members.borrow_mut())?;
+                    property.bind(&self_rc, &mut members.borrow_mut(), errors);
                 }
-
-                Ok(())
             }
-            TypeNode::Identifier(identifier) => {
-                identifier.borrow().bind(&self_rc)?;
+            TypeNode::Identifier { parent, name, .. } => {
+                *parent.borrow_mut() = Some(parent_weak);
 
-                Ok(())
+                name.borrow().bind(&self_rc);
             }
             TypeNode::SignatureDeclaration {
                 parent,
@@ -141,20 +194,19 @@ impl TypeNode {
                 parameters,
                 typename,
                 locals,
+                ..
             } => {
                 *parent.borrow_mut() = Some(parent_weak);
 
                 for type_parameter in type_parameters.borrow().iter() {
-                    type_parameter.bind(&self_rc, &mut locals.borrow_mut())?;
+                    type_parameter.bind(&self_rc, &mut locals.borrow_mut(), errors);
                 }
 
                 for parameter in parameters.borrow().iter() {
-                    parameter.bind(&self_rc, &mut locals.borrow_mut())?;
+                    parameter.bind(&self_rc, &mut locals.borrow_mut(), errors);
                 }
 
-                typename.borrow().bind(&self_rc)?;
-
-                Ok(())
+                typename.borrow().bind(&self_rc, errors);
             }
         }
     }
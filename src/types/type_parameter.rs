@@ -1,15 +1,19 @@
 use crate::binder::{
-    create_child, create_empty_parent, declare_symbol, AstNode, Child, Meaning, Parent, Table,
+    create_child, create_empty_parent, declare_symbol, get_parent, AstNode, Child, Meaning, Parent,
+    Table,
 };
 use crate::errors::{BindingError, ParsingError};
-use crate::lexer::Lexer;
+use crate::lexer::Span;
+use crate::parser::Parser;
 use crate::types::identifier::Identifier;
+use std::any::Any;
 use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct TypeParameter {
-    parent: Parent,
-    name: Child<Identifier>,
+    pub span: Span,
+    pub parent: Parent,
+    pub name: Child<Identifier>,
 }
 
 impl AstNode for TypeParameter {
@@ -20,13 +24,26 @@ impl AstNode for TypeParameter {
     fn get_name(&self) -> String {
         self.name.borrow().text.clone()
     }
+
+    fn get_span(&self) -> Span {
+        self.span
+    }
+
+    fn get_parent(&self) -> Option<Rc<dyn AstNode>> {
+        get_parent(&self.parent)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl TypeParameter {
-    pub fn parse(lexer: &mut Lexer) -> Result<TypeParameter, ParsingError> {
-        let name = Identifier::parse(lexer)?;
+    pub fn parse(parser: &mut Parser) -> Result<TypeParameter, ParsingError> {
+        let name = Identifier::parse(parser)?;
 
         Ok(TypeParameter {
+            span: name.span,
             parent: create_empty_parent(),
             name: create_child(name),
         })
@@ -36,15 +53,14 @@ impl TypeParameter {
         self: &Rc<Self>,
         parent: &Rc<dyn AstNode>,
         locals: &mut Table,
-    ) -> Result<(), BindingError> {
+        errors: &mut Vec<BindingError>,
+    ) {
         let parent_weak = Rc::downgrade(parent);
         let self_rc = Rc::clone(self) as Rc<dyn AstNode>;
         *self.parent.borrow_mut() = Some(parent_weak);
 
-        self.name.borrow().bind(&self_rc)?;
-
-        declare_symbol(locals, &self_rc)?;
+        self.name.borrow().bind(&self_rc);
 
-        Ok(())
+        declare_symbol(locals, &self_rc, errors);
     }
 }
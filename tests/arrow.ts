@@ -0,0 +1,19 @@
+var identity = (x: number) => x;
+var double = x => x * 2;
+var add = (a: number, b: number): number => {
+    return a + b;
+};
+var generic = <T>(value: T): T => value;
+var load = async (url: string) => url;
+var loadOne = async url => url;
+var async = 1;
+var named = async => async;
+
+var n: number = identity(1) + add(2, 3);
+var grouped = (n + 1) * 2;
+var curried = (a: number) => (b: number) => a + b;
+var three: number = curried(1)(2);
+
+// Every arrow has its own scope for its parameters
+var shadow = (n: string) => n;
+var s: string = shadow("a");
@@ -0,0 +1,11 @@
+var identity = (x: number) => x;
+var mismatch: string = identity(1);
+var wrongArgument = identity("a");
+
+// Parameters stay inside the arrow
+var leak = x;
+
+// `async` followed by a line break is not an async arrow
+var x2 = 1;
+var notAsync = async
+(x2) => x2;
@@ -0,0 +1,30 @@
+var a = 1
+var b: number = a
+a = 2; b = 3
+
+var f = function (x: number): number {
+    return x
+}
+
+var g = function (): void {
+    return
+}
+
+function h() { return 1 } h()
+
+// Not two statements, this calls `f`
+var c: number = f
+(1)
+;;
+type T = number
+
+// A function declaration ends at its `}`, these are new statements
+function i() { return 1 }
+(1)
+function j() { return 1 }
+-1
+
+// `type` followed by a line break is an identifier, not an alias
+var type = 1
+type
+= 2
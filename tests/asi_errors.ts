@@ -0,0 +1,5 @@
+// A line break after `return` ends the statement, so this returns nothing
+function f(): number {
+    return
+    1
+}
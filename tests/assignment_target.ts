@@ -0,0 +1,23 @@
+var a = 1;
+var o = { x: 1 };
+
+a = 2;
+(a) = 3;
+o.x += 1;
+o["x"] = 4;
+
+"lol" = "haha";
+1 += 2;
+a + 1 = 5;
+o.x() = 6;
+
+function f() {
+    return 1;
+}
+
+a++;
+--o.x;
+1++;
+++1;
+"s"++;
+f()++;
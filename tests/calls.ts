@@ -0,0 +1,16 @@
+var g = function(a: number, b: number) {
+    return a;
+};
+function f(a: number) {
+    return a;
+}
+
+var ok: number = g(1, 2) + f(3);
+
+var tooFew = g(1);
+var tooMany = f(1, 2, 3);
+
+// Without a `return` a function returns `void`
+function nothing() {}
+var n: number = nothing();
+var v: void = nothing();
@@ -6,5 +6,21 @@ function fun() {
 
 fun();
 
-// this does not work, because of parser
-// fun()();
\ No newline at end of file
+fun()();
+
+function make<T>() {
+    return function(x: T) {
+        return { y: function() { return x } };
+    };
+}
+
+var chained = make<number>()(1).y();
+
+function point() {
+    return function(x: number) {
+        return { label: function(): string { return "x" }, x: x };
+    };
+}
+
+var label: string = point()(1).label();
+var x: number = point()(2).x;
@@ -0,0 +1,8 @@
+function point() {
+    return function(x: number) {
+        return { label: function(): string { return "x" } };
+    };
+}
+
+var label: number = point()(1).label();
+var missing = point()(1).nope();
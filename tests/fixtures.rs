@@ -0,0 +1,217 @@
+use mini_compiler::diagnostics::locate;
+use mini_compiler::{run_checker, SourceMap};
+use std::fs;
+
+fn check_fixture(path: &str) -> Vec<String> {
+    let mut sources = SourceMap::new();
+    let file_id = sources.add(path, fs::read_to_string(path).unwrap());
+    let file = sources.get(file_id);
+
+    run_checker(file)
+        .iter()
+        .map(|diagnostic| diagnostic.render(&sources))
+        .collect()
+}
+
+/// Every diagnostic of the fixture as `line:column: message`.
+fn check_fixture_messages(path: &str) -> Vec<String> {
+    let text = fs::read_to_string(path).unwrap();
+    let mut sources = SourceMap::new();
+    let file_id = sources.add(path, text.as_str());
+
+    run_checker(sources.get(file_id))
+        .iter()
+        .map(|diagnostic| {
+            let location = locate(&text, diagnostic.span.start);
+            format!(
+                "{}:{}: {}",
+                location.line, location.column, diagnostic.message
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn complex() {
+    assert_eq!(
+        check_fixture("tests/complex.ts"),
+        vec![concat!(
+            "error: Type 'string' is not assignable to type 'number'.\n",
+            " --> tests/complex.ts:3:1\n",
+            "  |\n",
+            "3 | x = \"haha\";\n",
+            "  | ^\n",
+        )]
+    );
+}
+
+#[test]
+fn double_call() {
+    assert_eq!(check_fixture("tests/double_call.ts"), Vec::<String>::new());
+}
+
+#[test]
+fn double_call_errors() {
+    // The return type is followed through the whole chain of calls and accesses
+    assert_eq!(
+        check_fixture_messages("tests/double_call_errors.ts"),
+        vec![
+            "7:21: Type 'string' is not assignable to type 'number'.",
+            "8:26: Property 'nope' does not exist on type '{ label: () => string }'.",
+        ]
+    );
+}
+
+#[test]
+fn undeclared() {
+    assert_eq!(
+        check_fixture_messages("tests/undeclared.ts"),
+        vec![
+            "1:1: Cannot find name 'y'.",
+            "2:8: Cannot find name 'Foo'.",
+            "8:15: Cannot find name 'g'.",
+        ]
+    );
+}
+
+#[test]
+fn recovery() {
+    // Every syntax error is reported, and the statements after them are still checked
+    assert_eq!(
+        check_fixture_messages("tests/recovery.ts"),
+        vec![
+            "1:17: Expected expression but found `;`.",
+            "2:14: Expected expression but found `;`.",
+            "3:10: Expected identifier but found `;`.",
+            "8:13: Expected expression but found `]`.",
+            "5:17: Type 'number' is not assignable to type 'string'.",
+            "9:21: Type 'string' is not assignable to type 'number'.",
+        ]
+    );
+}
+
+#[test]
+fn redeclare() {
+    assert_eq!(
+        check_fixture("tests/redeclare.ts"),
+        vec![concat!(
+            "error: Cannot redeclare 'x'.\n",
+            " --> tests/redeclare.ts:2:5\n",
+            "  |\n",
+            "2 | var x = 2;\n",
+            "  |     ^\n",
+            "note: 'x' was also declared here.\n",
+            " --> tests/redeclare.ts:1:5\n",
+            "  |\n",
+            "1 | var x = 1;\n",
+            "  |     ^\n",
+        )]
+    );
+}
+
+#[test]
+fn literals() {
+    assert_eq!(
+        check_fixture_messages("tests/literals.ts"),
+        vec![
+            "6:19: Type 'null' is not assignable to type 'number'.",
+            "7:23: Type 'undefined' is not assignable to type 'string'.",
+        ]
+    );
+}
+
+#[test]
+fn asi() {
+    assert_eq!(check_fixture("tests/asi.ts"), Vec::<String>::new());
+}
+
+#[test]
+fn asi_errors() {
+    assert_eq!(
+        check_fixture_messages("tests/asi_errors.ts"),
+        vec!["3:5: Type 'void' is not assignable to type 'number'."]
+    );
+}
+
+#[test]
+fn operators() {
+    assert_eq!(check_fixture("tests/operators.ts"), Vec::<String>::new());
+}
+
+#[test]
+fn operators_errors() {
+    assert_eq!(
+        check_fixture_messages("tests/operators_errors.ts"),
+        vec![
+            "5:18: Operator `-` cannot be applied to types 'string' and 'number'.",
+            "6:11: Operator `+` cannot be applied to types 'number' and 'boolean'.",
+            "7:12: Operator `<` cannot be applied to types 'number' and 'string'.",
+            "8:15: Operator `>>>` cannot be applied to types 'bigint' and 'bigint'.",
+            "9:13: Operator `*` cannot be applied to types 'bigint' and 'number'.",
+            "11:16: An arithmetic operand must be of type 'any', 'number' or 'bigint', found 'string'.",
+            "12:16: An arithmetic operand must be of type 'any', 'number' or 'bigint', found 'boolean'.",
+            "13:1: An arithmetic operand must be of type 'any', 'number' or 'bigint', found 'string'.",
+        ]
+    );
+}
+
+#[test]
+fn properties() {
+    assert_eq!(
+        check_fixture_messages("tests/properties.ts"),
+        vec![
+            "11:21: Property 'z' does not exist on type '{ label: string, x: number, y: number }'.",
+            "12:1: Type 'number' is not assignable to type 'string'.",
+        ]
+    );
+}
+
+#[test]
+fn assignment_target() {
+    assert_eq!(
+        check_fixture_messages("tests/assignment_target.ts"),
+        vec![
+            "9:1: Invalid assignment target, it must be a variable or a property access.",
+            "10:1: Invalid assignment target, it must be a variable or a property access.",
+            "11:1: Invalid assignment target, it must be a variable or a property access.",
+            "12:1: Type 'number' has no call signatures.",
+            "12:1: Invalid assignment target, it must be a variable or a property access.",
+            "20:1: Invalid assignment target, it must be a variable or a property access.",
+            "21:3: Invalid assignment target, it must be a variable or a property access.",
+            "22:1: Invalid assignment target, it must be a variable or a property access.",
+            "22:1: An arithmetic operand must be of type 'any', 'number' or 'bigint', found 'string'.",
+            "23:1: Invalid assignment target, it must be a variable or a property access.",
+        ]
+    );
+}
+
+#[test]
+fn arrow() {
+    assert_eq!(check_fixture("tests/arrow.ts"), Vec::<String>::new());
+}
+
+#[test]
+fn arrow_errors() {
+    assert_eq!(
+        check_fixture_messages("tests/arrow_errors.ts"),
+        vec![
+            "11:6: Expected `;` but found `=>`.",
+            "2:24: Type 'number' is not assignable to type 'string'.",
+            "3:30: Type 'string' is not assignable to type 'number'.",
+            "6:12: Cannot find name 'x'.",
+            "10:16: Cannot find name 'async'.",
+        ]
+    );
+}
+
+#[test]
+fn calls() {
+    assert_eq!(
+        check_fixture_messages("tests/calls.ts"),
+        vec![
+            "10:14: Expected 2 arguments, but got 1.",
+            "11:15: Expected 1 arguments, but got 3.",
+            "15:17: Type 'void' is not assignable to type 'number'.",
+        ]
+    );
+}
@@ -0,0 +1,7 @@
+var n: null = null;
+var u: undefined = undefined;
+var t = this;
+var b: boolean = true;
+
+var bad: number = null;
+var alsoBad: string = undefined;
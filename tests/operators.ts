@@ -0,0 +1,28 @@
+var a: number = 1 + 2 * 3 - 4 / 2 % 3;
+var b = 2 ** 3 ** 2;
+var big = 10n * 2n - 1n;
+
+var s: string = "a" + a;
+var c: boolean = a < b && a >= 1 || !(a === b);
+var d = (a >> 1) >>> 2 << 3 & 255 | 1 ^ 2;
+var e: number = -a + ~b;
+var t: string = typeof a;
+
+a += 1;
+a >>= 1;
+s += "b";
+big **= 2n;
+
+a++;
+--a;
+
+// A line break before `++` ends the statement, so this increments `b`, not `a`
+a
+++b
+
+function id<T>(x: T): T {
+    return x;
+}
+
+var call = id<number>(a) < a;
+var compare = a < b == b > a;
@@ -0,0 +1,13 @@
+var s = "a";
+var n = 1;
+var b = true;
+
+var difference = s - n;
+var sum = n + b;
+var less = n < s;
+var shifted = 1n >>> 2n;
+var mixed = 1n * n;
+
+var negated = -s;
+var flipped = ~b;
+s++;
@@ -0,0 +1,12 @@
+var point = { x: 1, y: 2, label: "origin" };
+var x: number = point.x;
+var label: string = point["label"];
+
+point.x = 3;
+point["y"] += 1;
+
+var box: { size: number } = { size: 1 };
+box.size = box.size * 2;
+
+var missing = point.z;
+point.label = 1;
@@ -0,0 +1,10 @@
+var a: number = ;
+var b = (1 + ;
+type T = ;
+
+var c: string = 1;
+
+function f() {
+    var d = ];
+    var e: number = "e";
+}
@@ -0,0 +1,8 @@
+y = 1;
+var x: Foo = 1;
+
+// The name of a function expression is only in scope inside of it
+var f = function g(n: number): number {
+    return g(n);
+};
+var outside = g;